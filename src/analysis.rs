@@ -0,0 +1,6 @@
+pub mod checker;
+pub mod jack_type;
+pub mod lint;
+pub mod os;
+pub mod registry;
+pub mod suggestion;
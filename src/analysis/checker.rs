@@ -0,0 +1,954 @@
+use crate::analysis::jack_type::Type;
+use crate::analysis::lint::{Lint, LintLevels};
+use crate::analysis::registry::Registry;
+use crate::analysis::suggestion::suggest;
+use crate::ast::class::{Class, ClassVarKind, SubroutineDec, SubroutineKind};
+use crate::ast::expression::{
+    BinaryExpression, BinaryOp, Expression, KeywordConstant, SubroutineCall, Term, UnaryOp,
+    MAX_INTEGER_CONSTANT,
+};
+use crate::ast::identifier::Identifier;
+use crate::ast::statement::{ReturnStatement, Statement};
+use crate::ast::type_name::TypeName;
+use crate::error::diagnostic::Diagnostic;
+use crate::error::diagnostics::Diagnostics;
+use crate::symbol_table::kind::Kind;
+use crate::symbol_table::symbol_tables::SymbolTables;
+use crate::tokenizer::span::Span;
+
+/// Finds the semantic errors of a parsed class before any code is generated.
+pub struct Checker<'a> {
+    file_name: &'a str,
+    lint_levels: &'a LintLevels,
+    symbol_tables: SymbolTables,
+    registry: &'a Registry,
+    /// The kind and return type of the subroutine being checked
+    subroutine_kind: SubroutineKind,
+    return_type: Type,
+    diagnostics: &'a mut Diagnostics,
+}
+
+impl<'a> Checker<'a> {
+    pub fn check(
+        class: &Class,
+        registry: &'a Registry,
+        file_name: &'a str,
+        lint_levels: &'a LintLevels,
+        diagnostics: &'a mut Diagnostics,
+    ) {
+        let mut checker = Checker {
+            file_name,
+            lint_levels,
+            symbol_tables: SymbolTables::new(),
+            registry,
+            subroutine_kind: SubroutineKind::Function,
+            return_type: Type::Void,
+            diagnostics,
+        };
+        checker.check_class(class);
+    }
+
+    fn check_class(&mut self, class: &Class) {
+        self.symbol_tables.class_name = String::from(&class.name.name);
+        for class_var_dec in &class.class_var_decs {
+            let kind = match class_var_dec.kind {
+                ClassVarKind::Static => Kind::Static,
+                ClassVarKind::Field => Kind::Field,
+            };
+            self.check_type(&class_var_dec.type_name, &class_var_dec.var_names[0].span);
+            for var_name in &class_var_dec.var_names {
+                self.declare(var_name, &class_var_dec.type_name.to_string(), &kind);
+            }
+        }
+
+        for subroutine_dec in &class.subroutine_decs {
+            self.check_subroutine_dec(subroutine_dec, &class.name);
+        }
+    }
+
+    fn check_subroutine_dec(&mut self, subroutine_dec: &SubroutineDec, class_name: &Identifier) {
+        self.symbol_tables.start_subroutine();
+        self.subroutine_kind = subroutine_dec.kind;
+        self.return_type = match &subroutine_dec.return_type {
+            Some(type_name) => Type::from(type_name),
+            None => Type::Void,
+        };
+        if subroutine_dec.kind == SubroutineKind::Method {
+            self.symbol_tables
+                .define("this", &class_name.name, &Kind::Argument, &class_name.span);
+        }
+        for parameter in &subroutine_dec.parameters {
+            self.check_type(&parameter.type_name, &parameter.var_name.span);
+            self.declare(
+                &parameter.var_name,
+                &parameter.type_name.to_string(),
+                &Kind::Argument,
+            );
+        }
+        for var_dec in &subroutine_dec.body.var_decs {
+            self.check_type(&var_dec.type_name, &var_dec.var_names[0].span);
+            for var_name in &var_dec.var_names {
+                self.declare(var_name, &var_dec.type_name.to_string(), &Kind::Var);
+            }
+        }
+
+        self.check_statements(&subroutine_dec.body.statements);
+    }
+
+    fn check_statements(&mut self, statements: &[Statement]) {
+        for statement in statements {
+            self.check_statement(statement);
+        }
+    }
+
+    fn check_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Let(statement) => {
+                let var_type = self.check_variable(&statement.var_name);
+                let target = match &statement.index {
+                    Some(index) => {
+                        self.check_index(&var_type, &statement.var_name.span, index);
+                        Type::Unknown
+                    }
+                    None => var_type,
+                };
+                let value = self.check_expression(&statement.value);
+                self.expect_type(&target, &value, &statement.value.span());
+            }
+            Statement::If(statement) => {
+                self.check_condition(&statement.condition);
+                self.check_statements(&statement.statements);
+                if let Some(else_statements) = &statement.else_statements {
+                    self.check_statements(else_statements);
+                }
+            }
+            Statement::While(statement) => {
+                self.check_condition(&statement.condition);
+                self.check_statements(&statement.statements);
+            }
+            Statement::Do(statement) => {
+                self.check_subroutine_call(&statement.subroutine_call);
+            }
+            Statement::Return(statement) => self.check_return(statement),
+        }
+    }
+
+    fn check_condition(&mut self, condition: &Expression) {
+        let condition_type = self.check_expression(condition);
+        self.expect_type(&Type::Boolean, &condition_type, &condition.span());
+    }
+
+    fn check_return(&mut self, statement: &ReturnStatement) {
+        match (&statement.value, self.return_type.clone()) {
+            (Some(value), Type::Void) => {
+                self.check_expression(value);
+                let diagnostic = Diagnostic::new(
+                    "cannot return a value from a void subroutine",
+                    self.file_name,
+                    &value.span(),
+                );
+                self.report(diagnostic);
+            }
+            (Some(value), return_type) => {
+                let value_type = self.check_expression(value);
+                self.expect_type(&return_type, &value_type, &value.span());
+            }
+            (None, Type::Void) => {}
+            (None, return_type) => {
+                let diagnostic = Diagnostic::new(
+                    &format!("expected a return value of type `{return_type}`"),
+                    self.file_name,
+                    &statement.span,
+                );
+                self.report(diagnostic);
+            }
+        }
+    }
+
+    /// Checks an expression and returns its type.
+    fn check_expression(&mut self, expression: &Expression) -> Type {
+        match expression {
+            Expression::Term(term) => self.check_term(term),
+            Expression::Binary(binary) => self.check_binary(binary),
+        }
+    }
+
+    fn check_binary(&mut self, binary: &BinaryExpression) -> Type {
+        self.check_precedence(binary);
+        let lhs = self.check_expression(&binary.lhs);
+        let rhs = self.check_expression(&binary.rhs);
+        let result = match binary.op {
+            BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div => {
+                (lhs.is_numeric() && rhs.is_numeric()).then_some(Type::Int)
+            }
+            BinaryOp::Lt | BinaryOp::Gt => {
+                (lhs.is_numeric() && rhs.is_numeric()).then_some(Type::Boolean)
+            }
+            // bitwise on numbers, logical on booleans
+            BinaryOp::And | BinaryOp::Or => match (&lhs, &rhs) {
+                (Type::Unknown, other) | (other, Type::Unknown)
+                    if other.is_numeric() || other.is_boolean() =>
+                {
+                    Some(other.clone())
+                }
+                _ if lhs.is_numeric() && rhs.is_numeric() => Some(Type::Int),
+                _ if lhs.is_boolean() && rhs.is_boolean() => Some(Type::Boolean),
+                _ => None,
+            },
+            BinaryOp::Eq => {
+                (lhs.is_assignable_to(&rhs) || rhs.is_assignable_to(&lhs)).then_some(Type::Boolean)
+            }
+            BinaryOp::LogicalAnd | BinaryOp::LogicalOr => {
+                (lhs.is_boolean() && rhs.is_boolean()).then_some(Type::Boolean)
+            }
+        };
+
+        result.unwrap_or_else(|| {
+            let diagnostic = Diagnostic::new(
+                &format!(
+                    "cannot apply `{}` to `{lhs}` and `{rhs}`",
+                    binary.op.symbol()
+                ),
+                self.file_name,
+                &binary.op_span,
+            );
+            self.lint(Lint::TypeMismatch, diagnostic);
+            Type::Unknown
+        })
+    }
+
+    /// Warns about `a + b * c`, which is `(a + b) * c` in Jack.
+    /// Under the usual precedence the lhs never holds an operator binding less tightly, so nothing is reported.
+    fn check_precedence(&mut self, binary: &BinaryExpression) {
+        if let Expression::Binary(lhs) = &*binary.lhs {
+            if lhs.op.precedence() < binary.op.precedence() {
+                let diagnostic = Diagnostic::new(
+                    &format!(
+                        "`{}` is applied before `{}`",
+                        lhs.op.symbol(),
+                        binary.op.symbol()
+                    ),
+                    self.file_name,
+                    &binary.span,
+                )
+                .with_help("Jack applies operators from left to right, add parentheses to make it explicit");
+                self.lint(Lint::Precedence, diagnostic);
+            }
+        }
+    }
+
+    fn check_term(&mut self, term: &Term) -> Type {
+        match term {
+            Term::IntegerConstant(value, span) => {
+                if *value > MAX_INTEGER_CONSTANT {
+                    self.report_integer_out_of_range(span);
+                }
+                Type::Int
+            }
+            // the lexer already reported bit patterns that don't fit in 16 bits
+            Term::BitPattern(..) => Type::Int,
+            Term::StringConstant(..) => Type::Class(String::from("String")),
+            Term::CharConstant(..) => Type::Char,
+            Term::KeywordConstant(keyword, _) => match keyword {
+                KeywordConstant::True | KeywordConstant::False => Type::Boolean,
+                KeywordConstant::Null => Type::Null,
+                KeywordConstant::This => Type::Class(String::from(&self.symbol_tables.class_name)),
+            },
+            Term::VarName(var_name) => self.check_variable(var_name),
+            Term::ArrayElement(var_name, index, _) => {
+                let var_type = self.check_variable(var_name);
+                self.check_index(&var_type, &var_name.span, index);
+                Type::Unknown
+            }
+            Term::SubroutineCall(subroutine_call) => self.check_subroutine_call(subroutine_call),
+            Term::Parenthesized(expression, _) => self.check_expression(expression),
+            _ if term.is_min_integer() => Type::Int,
+            Term::Unary(op, term, span) => {
+                let term_type = self.check_term(term);
+                match op {
+                    UnaryOp::Neg if term_type.is_numeric() => Type::Int,
+                    UnaryOp::Not if term_type.is_numeric() || term_type.is_boolean() => term_type,
+                    _ => {
+                        let diagnostic = Diagnostic::new(
+                            &format!("cannot apply `{}` to `{term_type}`", op.symbol()),
+                            self.file_name,
+                            span,
+                        );
+                        self.lint(Lint::TypeMismatch, diagnostic);
+                        Type::Unknown
+                    }
+                }
+            }
+        }
+    }
+
+    fn report_integer_out_of_range(&mut self, span: &Span) {
+        let diagnostic = Diagnostic::new("integer constant out of range", self.file_name, span)
+            .with_help(&format!(
+                "integer constants must be at most {MAX_INTEGER_CONSTANT}, or {} when negated",
+                MAX_INTEGER_CONSTANT + 1
+            ));
+        self.report(diagnostic);
+    }
+
+    /// Checks `var[index]`, where `var` must be an array and `index` a number.
+    fn check_index(&mut self, var_type: &Type, var_span: &Span, index: &Expression) {
+        if !var_type.is_assignable_to(&Type::Class(String::from("Array"))) {
+            let diagnostic = Diagnostic::new(
+                &format!("cannot index into a value of type `{var_type}`"),
+                self.file_name,
+                var_span,
+            );
+            self.lint(Lint::TypeMismatch, diagnostic);
+        }
+        let index_type = self.check_expression(index);
+        self.expect_type(&Type::Int, &index_type, &index.span());
+    }
+
+    /// Checks a call against the signature of the subroutine it calls and returns the type of its result.
+    /// A variable of a class missing from the registry is reported where it is declared, so calls on it aren't checked.
+    fn check_subroutine_call(&mut self, subroutine_call: &SubroutineCall) -> Type {
+        let name = &subroutine_call.subroutine_name;
+        // the class to look the subroutine up in, and whether it is called on an object
+        let callee = match &subroutine_call.receiver {
+            None => Some((String::from(&self.symbol_tables.class_name), true)),
+            Some(receiver) => match self.symbol_tables.type_of(&receiver.name) {
+                Some(type_name) => match Type::from(&TypeName::from(&type_name)) {
+                    Type::Class(class_name) => Some((class_name, true)),
+                    // not a lint: the call would compile to `call int.x`, which no class defines
+                    receiver_type => {
+                        let diagnostic = Diagnostic::new(
+                            &format!(
+                                "cannot call method `{}` on type `{receiver_type}`",
+                                name.name
+                            ),
+                            self.file_name,
+                            &receiver.span,
+                        );
+                        self.report(diagnostic);
+                        None
+                    }
+                },
+                None if self.registry.class(&receiver.name).is_some() => {
+                    Some((String::from(&receiver.name), false))
+                }
+                // neither a variable nor a class, so a misspelled one of them
+                None if receiver.name.starts_with(|c: char| c.is_ascii_uppercase()) => {
+                    self.report_unknown_class(receiver);
+                    None
+                }
+                None => {
+                    self.check_variable(receiver);
+                    None
+                }
+            },
+        };
+
+        let argument_types: Vec<Type> = subroutine_call
+            .arguments
+            .iter()
+            .map(|argument| self.check_expression(argument))
+            .collect();
+
+        let registry = self.registry;
+        let Some((class_name, on_object)) = callee else {
+            return Type::Unknown;
+        };
+        let Some(class_signature) = registry.class(&class_name) else {
+            return Type::Unknown;
+        };
+        let Some(signature) = class_signature.subroutine(&name.name) else {
+            let mut diagnostic = Diagnostic::new(
+                &format!("no subroutine `{}` in class `{class_name}`", name.name),
+                self.file_name,
+                &name.span,
+            );
+            let names = class_signature.subroutine_names().map(|name| name.as_str());
+            if let Some(suggestion) = suggest(&name.name, names) {
+                diagnostic = diagnostic.with_help(&format!("did you mean `{suggestion}`?"));
+            }
+            self.report(diagnostic);
+            return Type::Unknown;
+        };
+
+        let full_name = format!("{class_name}.{}", name.name);
+        if on_object && signature.kind != SubroutineKind::Method {
+            let diagnostic = Diagnostic::new(
+                &format!("`{full_name}` is a {}, not a method", signature.kind),
+                self.file_name,
+                &name.span,
+            )
+            .with_help(&format!("call it as `{full_name}`"));
+            self.report(diagnostic);
+        } else if !on_object && signature.kind == SubroutineKind::Method {
+            let diagnostic = Diagnostic::new(
+                &format!("method `{full_name}` must be called on an object"),
+                self.file_name,
+                &name.span,
+            );
+            self.report(diagnostic);
+        } else if subroutine_call.receiver.is_none()
+            && self.subroutine_kind == SubroutineKind::Function
+        {
+            let diagnostic = Diagnostic::new(
+                &format!("cannot call method `{}` from a function", name.name),
+                self.file_name,
+                &name.span,
+            )
+            .with_help("a function has no `this` to call the method on");
+            self.report(diagnostic);
+        }
+
+        if argument_types.len() != signature.parameters.len() {
+            let diagnostic = Diagnostic::new(
+                &format!(
+                    "`{full_name}` takes {} argument(s) but {} were given",
+                    signature.parameters.len(),
+                    argument_types.len()
+                ),
+                self.file_name,
+                &subroutine_call.span,
+            );
+            self.report(diagnostic);
+        }
+
+        let own_class = class_name == self.symbol_tables.class_name;
+        for ((argument, argument_type), parameter) in subroutine_call
+            .arguments
+            .iter()
+            .zip(&argument_types)
+            .zip(&signature.parameters)
+        {
+            let parameter_type = Type::from(&parameter.type_name);
+            if !argument_type.is_assignable_to(&parameter_type) {
+                let mut diagnostic = Self::mismatch(
+                    &parameter_type,
+                    argument_type,
+                    self.file_name,
+                    &argument.span(),
+                );
+                // notes can only point into the file being checked
+                if own_class {
+                    diagnostic = diagnostic.with_note(
+                        &format!("parameter `{}` is declared here", parameter.var_name.name),
+                        &parameter.var_name.span,
+                    );
+                }
+                self.lint(Lint::TypeMismatch, diagnostic);
+            }
+        }
+
+        match &signature.return_type {
+            Some(type_name) => Type::from(type_name),
+            None => Type::Void,
+        }
+    }
+
+    /// Reports a value whose type doesn't fit the expected one.
+    fn expect_type(&mut self, expected: &Type, actual: &Type, span: &Span) {
+        if !actual.is_assignable_to(expected) {
+            let diagnostic = Self::mismatch(expected, actual, self.file_name, span);
+            self.lint(Lint::TypeMismatch, diagnostic);
+        }
+    }
+
+    fn mismatch(expected: &Type, actual: &Type, file_name: &str, span: &Span) -> Diagnostic {
+        Diagnostic::new(
+            &format!("mismatched types: expected `{expected}`, found `{actual}`"),
+            file_name,
+            span,
+        )
+    }
+
+    /// Defines a variable, reporting a name declared twice in the same scope
+    /// and a local or argument that hides a field or static.
+    fn declare(&mut self, var_name: &Identifier, type_name: &str, kind: &Kind) {
+        let name = &var_name.name;
+        let duplicate = self
+            .symbol_tables
+            .define(name, type_name, kind, &var_name.span)
+            .map(|symbol| symbol.span);
+        if let Some(span) = duplicate {
+            let diagnostic = Diagnostic::new(
+                &format!("duplicate declaration of `{name}`"),
+                self.file_name,
+                &var_name.span,
+            )
+            .with_note(&format!("`{name}` is first declared here"), &span);
+            self.report(diagnostic);
+            return;
+        }
+
+        if kind == &Kind::Argument || kind == &Kind::Var {
+            let shadowed = self
+                .symbol_tables
+                .get_class_symbol(name)
+                .map(|symbol| (Self::describe(&symbol.kind), symbol.span));
+            if let Some((shadowed_kind, span)) = shadowed {
+                let diagnostic = Diagnostic::new(
+                    &format!(
+                        "{} `{name}` shadows {shadowed_kind} `{name}`",
+                        Self::describe(kind)
+                    ),
+                    self.file_name,
+                    &var_name.span,
+                )
+                .with_note(&format!("{shadowed_kind} `{name}` is declared here"), &span);
+                self.lint(Lint::Shadowing, diagnostic);
+            }
+        }
+    }
+
+    fn describe(kind: &Kind) -> &'static str {
+        match kind {
+            Kind::Static => "static",
+            Kind::Field => "field",
+            Kind::Argument => "argument",
+            Kind::Var => "local variable",
+        }
+    }
+
+    fn report_unknown_class(&mut self, class_name: &Identifier) {
+        let mut diagnostic = Diagnostic::new(
+            &format!("no class `{}`", class_name.name),
+            self.file_name,
+            &class_name.span,
+        );
+        let names = self.registry.class_names().map(|name| name.as_str());
+        if let Some(suggestion) = suggest(&class_name.name, names) {
+            diagnostic = diagnostic.with_help(&format!("did you mean `{suggestion}`?"));
+        }
+        self.report(diagnostic);
+    }
+
+    /// Reports a declared class type missing from the registry, at the first variable declared with it.
+    fn check_type(&mut self, type_name: &TypeName, span: &Span) {
+        let TypeName::Class(class_name) = type_name else {
+            return;
+        };
+        if self.registry.class(class_name).is_some() {
+            return;
+        }
+
+        let mut diagnostic = Diagnostic::new(
+            &format!("unknown type `{class_name}`"),
+            self.file_name,
+            span,
+        );
+        let names = self.registry.class_names().map(|name| name.as_str());
+        if let Some(suggestion) = suggest(class_name, names) {
+            diagnostic = diagnostic.with_help(&format!("did you mean `{suggestion}`?"));
+        }
+        self.report(diagnostic);
+    }
+
+    /// Returns the type of a variable, reporting it with a similar name when it is missing from the symbol tables.
+    fn check_variable(&mut self, var_name: &Identifier) -> Type {
+        if let Some(type_name) = self.symbol_tables.type_of(&var_name.name) {
+            return Type::from(&TypeName::from(&type_name));
+        }
+
+        let mut diagnostic = Diagnostic::new(
+            &format!("undefined variable `{}`", var_name.name),
+            self.file_name,
+            &var_name.span,
+        );
+        let names = self.symbol_tables.names().map(|name| name.as_str());
+        if let Some(suggestion) = suggest(&var_name.name, names) {
+            diagnostic = diagnostic.with_help(&format!("did you mean `{suggestion}`?"));
+        }
+        self.report(diagnostic);
+        Type::Unknown
+    }
+
+    /// Reports a lint with the severity set for it, if it is not allowed.
+    fn lint(&mut self, lint: Lint, mut diagnostic: Diagnostic) {
+        if let Some(severity) = self.lint_levels.severity(lint) {
+            diagnostic.severity = severity;
+            self.report(diagnostic);
+        }
+    }
+
+    fn report(&mut self, diagnostic: Diagnostic) {
+        // warnings are kept even past the error limit, errors are not
+        if !diagnostic.is_error() || !self.diagnostics.is_full() {
+            self.diagnostics.push(diagnostic);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::analysis::checker::Checker;
+    use crate::analysis::lint::{Lint, LintLevels};
+    use crate::analysis::registry::Registry;
+    use crate::error::diagnostic::Diagnostic;
+    use crate::error::diagnostics::Diagnostics;
+    use crate::parsing::class_parser::ClassParser;
+    use crate::parsing::precedence::Precedence;
+    use crate::tokenizer::dialect::Dialect;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+    #[test]
+    fn can_report_undefined_variable() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Main {{").unwrap();
+        writeln!(src_file, "    field int counter;").unwrap();
+        writeln!(src_file, "    method void run(int step) {{").unwrap();
+        writeln!(src_file, "        var Array values;").unwrap();
+        writeln!(src_file, "        let countr = counter + step;").unwrap();
+        writeln!(src_file, "        let values[i] = 0;").unwrap();
+        writeln!(src_file, "        do Output.printInt(valeus[0]);").unwrap();
+        writeln!(src_file, "        return;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let class = ClassParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+        let mut registry = Registry::new();
+        registry.register(&class);
+        registry.register_os();
+        let mut diagnostics = Diagnostics::new(20);
+
+        Checker::check(
+            &class,
+            &registry,
+            "Main.jack",
+            &LintLevels::default(),
+            &mut diagnostics,
+        );
+        let actual: Vec<&Diagnostic> = diagnostics.iter().collect();
+
+        assert_eq!(3, actual.len());
+        assert_eq!("undefined variable `countr`", actual[0].message);
+        assert_eq!(Some("did you mean `counter`?"), actual[0].help.as_deref());
+        assert_eq!((5, 13), (actual[0].span.line, actual[0].span.column));
+        assert_eq!("undefined variable `i`", actual[1].message);
+        assert_eq!(None, actual[1].help);
+        assert_eq!(Some("did you mean `values`?"), actual[2].help.as_deref());
+    }
+
+    #[test]
+    fn can_report_unknown_receivers_and_types() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Main {{").unwrap();
+        writeln!(src_file, "    field Mains other;").unwrap();
+        writeln!(src_file, "    method void move(int dx, int dy) {{").unwrap();
+        writeln!(src_file, "        var Main point;").unwrap();
+        writeln!(src_file, "        do poit.move(1, 2);").unwrap();
+        writeln!(src_file, "        do point.move(1, 2);").unwrap();
+        writeln!(src_file, "        return;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let class = ClassParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+        let mut registry = Registry::new();
+        registry.register(&class);
+        let mut diagnostics = Diagnostics::new(20);
+
+        Checker::check(
+            &class,
+            &registry,
+            "Main.jack",
+            &LintLevels::default(),
+            &mut diagnostics,
+        );
+        let actual: Vec<&Diagnostic> = diagnostics.iter().collect();
+
+        assert_eq!(2, actual.len());
+        assert_eq!("unknown type `Mains`", actual[0].message);
+        assert_eq!(Some("did you mean `Main`?"), actual[0].help.as_deref());
+        assert_eq!((2, 17), (actual[0].span.line, actual[0].span.column));
+        assert_eq!("undefined variable `poit`", actual[1].message);
+        assert_eq!(Some("did you mean `point`?"), actual[1].help.as_deref());
+    }
+
+    #[test]
+    fn can_report_duplicate_declaration() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Main {{").unwrap();
+        writeln!(src_file, "    field int x;").unwrap();
+        writeln!(src_file, "    static boolean x;").unwrap();
+        writeln!(src_file, "    function void main(int y) {{").unwrap();
+        writeln!(src_file, "        var int x, y;").unwrap();
+        writeln!(src_file, "        return;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let class = ClassParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+        let mut registry = Registry::new();
+        registry.register(&class);
+        let mut diagnostics = Diagnostics::new(20);
+
+        Checker::check(
+            &class,
+            &registry,
+            "Main.jack",
+            &LintLevels::default(),
+            &mut diagnostics,
+        );
+        let actual: Vec<String> = diagnostics
+            .iter()
+            .map(|d| format!("{}: {d}", d.severity))
+            .collect();
+
+        assert_eq!(
+            vec![
+                "error: duplicate declaration of `x` at Main.jack:3:20\n  = note: `x` is first declared here at Main.jack:2:15",
+                "warning: local variable `x` shadows field `x` at Main.jack:5:17\n  = note: field `x` is declared here at Main.jack:2:15",
+                "error: duplicate declaration of `y` at Main.jack:5:20\n  = note: `y` is first declared here at Main.jack:4:28",
+            ],
+            actual
+        );
+    }
+
+    #[test]
+    fn can_report_type_errors() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Main {{").unwrap();
+        writeln!(src_file, "    function void main() {{").unwrap();
+        writeln!(src_file, "        var boolean b;").unwrap();
+        writeln!(src_file, "        var int n;").unwrap();
+        writeln!(src_file, "        let b = 5 + true;").unwrap();
+        writeln!(src_file, "        let n = Main.twice(\"ten\");").unwrap();
+        writeln!(src_file, "        do n.run();").unwrap();
+        writeln!(src_file, "        return n;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "    function int twice(int value) {{").unwrap();
+        writeln!(src_file, "        return value * 2;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let class = ClassParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+        let mut registry = Registry::new();
+        registry.register(&class);
+        let mut diagnostics = Diagnostics::new(20);
+
+        Checker::check(
+            &class,
+            &registry,
+            "Main.jack",
+            &LintLevels::default(),
+            &mut diagnostics,
+        );
+        let actual: Vec<String> = diagnostics
+            .iter()
+            .map(|d| format!("{}: {d}", d.severity))
+            .collect();
+
+        assert_eq!(
+            vec![
+                "warning: cannot apply `+` to `int` and `boolean` at Main.jack:5:19",
+                "warning: mismatched types: expected `int`, found `String` at Main.jack:6:28\n  = note: parameter `value` is declared here at Main.jack:10:28",
+                "error: cannot call method `run` on type `int` at Main.jack:7:12",
+                "error: cannot return a value from a void subroutine at Main.jack:8:16",
+            ],
+            actual
+        );
+    }
+
+    #[test]
+    fn can_report_operators_grouped_differently_than_usual() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Main {{").unwrap();
+        writeln!(src_file, "    function int main(int a, int b) {{").unwrap();
+        writeln!(src_file, "        if (a * 2 + b < 10) {{").unwrap();
+        writeln!(src_file, "            return a + b * 2;").unwrap();
+        writeln!(src_file, "        }}").unwrap();
+        writeln!(src_file, "        return a + (b * 2) - 1;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let class = ClassParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+        let mut registry = Registry::new();
+        registry.register(&class);
+        let mut diagnostics = Diagnostics::new(20);
+
+        Checker::check(
+            &class,
+            &registry,
+            "Main.jack",
+            &LintLevels::default(),
+            &mut diagnostics,
+        );
+        let actual: Vec<String> = diagnostics
+            .iter()
+            .map(|d| format!("{}: {d}", d.severity))
+            .collect();
+
+        assert_eq!(
+            vec!["warning: `+` is applied before `*` at Main.jack:4:20\n  = help: Jack applies operators from left to right, add parentheses to make it explicit"],
+            actual
+        );
+    }
+
+    #[test]
+    fn can_type_char_constants() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Main {{").unwrap();
+        writeln!(src_file, "    function void main() {{").unwrap();
+        writeln!(src_file, "        var String s;").unwrap();
+        writeln!(src_file, "        let s = 'A';").unwrap();
+        writeln!(src_file, "        return;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::with_dialect(path, Dialect::Extended).unwrap();
+        let class = ClassParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+        let mut registry = Registry::new();
+        registry.register(&class);
+        registry.register_os();
+        let mut diagnostics = Diagnostics::new(20);
+
+        Checker::check(
+            &class,
+            &registry,
+            "Main.jack",
+            &LintLevels::default(),
+            &mut diagnostics,
+        );
+        let actual: Vec<String> = diagnostics.iter().map(|d| d.message.clone()).collect();
+
+        assert_eq!(
+            vec!["mismatched types: expected `String`, found `char`"],
+            actual
+        );
+    }
+
+    #[test]
+    fn can_type_short_circuit_operators() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Main {{").unwrap();
+        writeln!(src_file, "    function boolean main(int a, boolean b) {{").unwrap();
+        writeln!(src_file, "        if ((a > 0) && b || ~b) {{").unwrap();
+        writeln!(src_file, "            return a || b;").unwrap();
+        writeln!(src_file, "        }}").unwrap();
+        writeln!(src_file, "        return b && 1;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::with_dialect(path, Dialect::Extended).unwrap();
+        let class = ClassParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+        let mut registry = Registry::new();
+        registry.register(&class);
+        let mut diagnostics = Diagnostics::new(20);
+
+        Checker::check(
+            &class,
+            &registry,
+            "Main.jack",
+            &LintLevels::default(),
+            &mut diagnostics,
+        );
+        let actual: Vec<String> = diagnostics.iter().map(|d| d.message.clone()).collect();
+
+        assert_eq!(
+            vec![
+                "cannot apply `||` to `int` and `boolean`",
+                "cannot apply `&&` to `boolean` and `int`",
+            ],
+            actual
+        );
+    }
+
+    #[test]
+    fn can_report_integer_constants_out_of_range() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Main {{").unwrap();
+        writeln!(src_file, "    function int main() {{").unwrap();
+        writeln!(src_file, "        var int x;").unwrap();
+        writeln!(src_file, "        let x = 32767 + -32768;").unwrap();
+        writeln!(src_file, "        let x = 40000 - 32768;").unwrap();
+        writeln!(src_file, "        return 123456789012345678901234567890;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let class = ClassParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+        let mut registry = Registry::new();
+        registry.register(&class);
+        let mut diagnostics = Diagnostics::new(20);
+
+        Checker::check(
+            &class,
+            &registry,
+            "Main.jack",
+            &LintLevels::default(),
+            &mut diagnostics,
+        );
+        let actual: Vec<String> = diagnostics
+            .iter()
+            .map(|d| format!("{}: {d}", d.severity))
+            .collect();
+
+        let help = "  = help: integer constants must be at most 32767, or 32768 when negated";
+        assert_eq!(
+            vec![
+                format!("error: integer constant out of range at Main.jack:5:17\n{help}"),
+                format!("error: integer constant out of range at Main.jack:5:25\n{help}"),
+                format!("error: integer constant out of range at Main.jack:6:16\n{help}"),
+            ],
+            actual
+        );
+    }
+
+    #[test]
+    fn can_report_lints_as_errors_in_strict_mode() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Main {{").unwrap();
+        writeln!(src_file, "    field int x;").unwrap();
+        writeln!(src_file, "    method void run(boolean x) {{").unwrap();
+        writeln!(src_file, "        while (1) {{}}").unwrap();
+        writeln!(src_file, "        return;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let class = ClassParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+        let mut registry = Registry::new();
+        registry.register(&class);
+        let mut diagnostics = Diagnostics::new(20);
+        let lint_levels = LintLevels::new(&[Lint::Shadowing], true);
+
+        Checker::check(
+            &class,
+            &registry,
+            "Main.jack",
+            &lint_levels,
+            &mut diagnostics,
+        );
+        let actual: Vec<String> = diagnostics
+            .iter()
+            .map(|d| format!("{}: {d}", d.severity))
+            .collect();
+
+        assert_eq!(
+            vec!["error: mismatched types: expected `boolean`, found `int` at Main.jack:4:16"],
+            actual
+        );
+    }
+}
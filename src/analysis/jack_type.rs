@@ -0,0 +1,91 @@
+use std::fmt::{Display, Formatter};
+
+use crate::ast::type_name::TypeName;
+
+/// The type of a Jack value as far as the checker can tell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Type {
+    Int,
+    Char,
+    Boolean,
+    /// A class, including `Array` and `String`
+    Class(String),
+    /// The type of `null`, which fits any class
+    Null,
+    /// The result of a void subroutine
+    Void,
+    /// A type that can't be known, such as the type of an array element
+    Unknown,
+}
+
+impl Type {
+    pub fn from(type_name: &TypeName) -> Self {
+        match type_name {
+            TypeName::Int => Type::Int,
+            TypeName::Char => Type::Char,
+            TypeName::Boolean => Type::Boolean,
+            TypeName::Class(name) => Type::Class(String::from(name)),
+        }
+    }
+
+    /// Returns true for the types arithmetic works on. `char` is a number in Jack.
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Type::Int | Type::Char | Type::Unknown)
+    }
+
+    pub fn is_boolean(&self) -> bool {
+        matches!(self, Type::Boolean | Type::Unknown)
+    }
+
+    /// Returns true if a value of this type can be stored in a variable of the target type.
+    /// `Array` is Jack's raw pointer, so it converts from and to `int` and any class.
+    pub fn is_assignable_to(&self, target: &Type) -> bool {
+        match (self, target) {
+            (Type::Unknown, _) | (_, Type::Unknown) => true,
+            (Type::Void, _) | (_, Type::Void) => false,
+            (Type::Int | Type::Char, Type::Int | Type::Char) => true,
+            (Type::Null, Type::Class(_)) => true,
+            (Type::Class(name), Type::Class(_)) | (Type::Class(_), Type::Class(name))
+                if name == "Array" =>
+            {
+                true
+            }
+            (Type::Int, Type::Class(name)) => name == "Array",
+            _ => self == target,
+        }
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Int => write!(f, "int"),
+            Type::Char => write!(f, "char"),
+            Type::Boolean => write!(f, "boolean"),
+            Type::Class(name) => write!(f, "{name}"),
+            Type::Null => write!(f, "null"),
+            Type::Void => write!(f, "void"),
+            Type::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::jack_type::Type;
+
+    #[test]
+    fn can_assign_compatible_types() {
+        let point = Type::Class(String::from("Point"));
+        let array = Type::Class(String::from("Array"));
+        assert!(Type::Char.is_assignable_to(&Type::Int));
+        assert!(Type::Null.is_assignable_to(&point));
+        assert!(array.is_assignable_to(&point));
+        assert!(Type::Int.is_assignable_to(&array));
+        assert!(Type::Unknown.is_assignable_to(&Type::Boolean));
+        assert!(!Type::Boolean.is_assignable_to(&Type::Int));
+        assert!(!Type::Class(String::from("String")).is_assignable_to(&Type::Int));
+        assert!(!Type::Int.is_assignable_to(&point));
+        assert!(!Type::Void.is_assignable_to(&Type::Int));
+    }
+}
@@ -0,0 +1,58 @@
+use clap::ValueEnum;
+
+use crate::error::severity::Severity;
+
+/// A kind of warning reported by the checker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Lint {
+    /// A local variable or argument named like a field or static
+    Shadowing,
+    /// A value whose type doesn't fit where it is used
+    TypeMismatch,
+    /// Operators without parentheses that the usual precedence would group differently
+    Precedence,
+}
+
+/// Decides how the checker reports each lint.
+#[derive(Debug, Default, Clone)]
+pub struct LintLevels {
+    allowed: Vec<Lint>,
+    strict: bool,
+}
+
+impl LintLevels {
+    /// `allowed` lints are not reported. With `strict`, the others are reported as errors.
+    pub fn new(allowed: &[Lint], strict: bool) -> Self {
+        LintLevels {
+            allowed: allowed.to_vec(),
+            strict,
+        }
+    }
+
+    /// Returns the severity of the lint, or None if it is allowed.
+    pub fn severity(&self, lint: Lint) -> Option<Severity> {
+        if self.allowed.contains(&lint) {
+            None
+        } else if self.strict {
+            Some(Severity::Error)
+        } else {
+            Some(Severity::Warning)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::lint::{Lint, LintLevels};
+    use crate::error::severity::Severity;
+
+    #[test]
+    fn can_decide_severity() {
+        let default = LintLevels::default();
+        assert_eq!(Some(Severity::Warning), default.severity(Lint::Shadowing));
+
+        let strict = LintLevels::new(&[Lint::Shadowing], true);
+        assert_eq!(None, strict.severity(Lint::Shadowing));
+        assert_eq!(Some(Severity::Error), strict.severity(Lint::TypeMismatch));
+    }
+}
@@ -0,0 +1,46 @@
+use crate::ast::class::Class;
+use crate::parsing::class_parser::ClassParser;
+use crate::parsing::precedence::Precedence;
+use crate::tokenizer::dialect::Dialect;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+/// Declarations of the Jack OS classes. Only their signatures are used, so the bodies are empty.
+const OS_SOURCES: [(&str, &str); 8] = [
+    ("Math.jack", include_str!("os/Math.jack")),
+    ("String.jack", include_str!("os/String.jack")),
+    ("Array.jack", include_str!("os/Array.jack")),
+    ("Output.jack", include_str!("os/Output.jack")),
+    ("Screen.jack", include_str!("os/Screen.jack")),
+    ("Keyboard.jack", include_str!("os/Keyboard.jack")),
+    ("Memory.jack", include_str!("os/Memory.jack")),
+    ("Sys.jack", include_str!("os/Sys.jack")),
+];
+
+/// Parses the declarations of the Jack OS classes.
+pub fn classes() -> Vec<Class> {
+    OS_SOURCES
+        .iter()
+        .map(|(file_name, code)| {
+            let mut tokenizer = JackTokenizer::from_source(file_name, code, 0, Dialect::Standard)
+                .expect("OS declarations should tokenize");
+            ClassParser::parse(&mut tokenizer, Precedence::Jack)
+                .expect("OS declarations should parse")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::os;
+
+    #[test]
+    fn can_parse_os_classes() {
+        let classes = os::classes();
+        let names: Vec<&str> = classes.iter().map(|c| c.name.name.as_str()).collect();
+
+        assert_eq!(
+            vec!["Math", "String", "Array", "Output", "Screen", "Keyboard", "Memory", "Sys"],
+            names
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use crate::analysis::os;
+use crate::ast::class::{Class, Parameter, SubroutineKind};
+use crate::ast::type_name::TypeName;
+
+/// The signatures of every class known to a program, by class name.
+#[derive(Debug, Default)]
+pub struct Registry {
+    classes: HashMap<String, ClassSignature>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Collects the signatures of a class.
+    /// Returns false, keeping the signatures already collected, when a class with the same name was registered before.
+    pub fn register(&mut self, class: &Class) -> bool {
+        if self.classes.contains_key(&class.name.name) {
+            return false;
+        }
+        self.classes
+            .insert(String::from(&class.name.name), ClassSignature::from(class));
+        true
+    }
+
+    /// Collects the signatures of the Jack OS classes that the program doesn't define itself.
+    pub fn register_os(&mut self) {
+        for class in os::classes() {
+            self.register(&class);
+        }
+    }
+
+    pub fn class(&self, name: &str) -> Option<&ClassSignature> {
+        self.classes.get(name)
+    }
+
+    pub fn class_names(&self) -> impl Iterator<Item = &String> {
+        self.classes.keys()
+    }
+}
+
+/// The subroutines of a class that other classes can call, by subroutine name.
+#[derive(Debug, Default)]
+pub struct ClassSignature {
+    subroutines: HashMap<String, SubroutineSignature>,
+}
+
+impl ClassSignature {
+    pub fn from(class: &Class) -> Self {
+        let subroutines = class
+            .subroutine_decs
+            .iter()
+            .map(|subroutine_dec| {
+                let signature = SubroutineSignature {
+                    kind: subroutine_dec.kind,
+                    return_type: subroutine_dec.return_type.clone(),
+                    parameters: subroutine_dec.parameters.clone(),
+                };
+                (String::from(&subroutine_dec.name.name), signature)
+            })
+            .collect();
+        ClassSignature { subroutines }
+    }
+
+    pub fn subroutine(&self, name: &str) -> Option<&SubroutineSignature> {
+        self.subroutines.get(name)
+    }
+
+    pub fn subroutine_names(&self) -> impl Iterator<Item = &String> {
+        self.subroutines.keys()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SubroutineSignature {
+    pub kind: SubroutineKind,
+    /// None if the subroutine is ’void’
+    pub return_type: Option<TypeName>,
+    pub parameters: Vec<Parameter>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::analysis::registry::Registry;
+    use crate::ast::class::SubroutineKind;
+    use crate::ast::type_name::TypeName;
+    use crate::parsing::class_parser::ClassParser;
+    use crate::parsing::precedence::Precedence;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+    #[test]
+    fn can_register_class() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Point {{").unwrap();
+        writeln!(src_file, "    field int x, y;").unwrap();
+        writeln!(src_file, "    constructor Point new(int ax, int ay) {{").unwrap();
+        writeln!(src_file, "        return this;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "    method void dispose() {{").unwrap();
+        writeln!(src_file, "        return;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let class = ClassParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+        let mut registry = Registry::new();
+        registry.register(&class);
+
+        let point = registry.class("Point").unwrap();
+        let new = point.subroutine("new").unwrap();
+        assert_eq!(SubroutineKind::Constructor, new.kind);
+        assert_eq!(
+            Some(TypeName::Class(String::from("Point"))),
+            new.return_type
+        );
+        assert_eq!(2, new.parameters.len());
+        assert_eq!(None, point.subroutine("dispose").unwrap().return_type);
+        assert!(point.subroutine("move").is_none());
+        assert!(registry.class("Main").is_none());
+    }
+
+    #[test]
+    fn can_register_os_classes() {
+        let mut registry = Registry::new();
+        registry.register_os();
+
+        let print_string = registry
+            .class("Output")
+            .and_then(|output| output.subroutine("printString"))
+            .unwrap();
+        assert_eq!(SubroutineKind::Function, print_string.kind);
+        assert_eq!(None, print_string.return_type);
+        assert_eq!(
+            TypeName::Class(String::from("String")),
+            print_string.parameters[0].type_name
+        );
+        let append_char = registry
+            .class("String")
+            .and_then(|string| string.subroutine("appendChar"))
+            .unwrap();
+        assert_eq!(SubroutineKind::Method, append_char.kind);
+    }
+}
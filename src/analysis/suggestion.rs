@@ -0,0 +1,47 @@
+/// Returns the candidate closest to the given name, if it is close enough to be a likely typo.
+pub fn suggest<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let max_distance = std::cmp::max(name.chars().count() / 3, 1);
+    candidates
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between two strings, counted in chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::suggestion::{edit_distance, suggest};
+
+    #[test]
+    fn can_measure_edit_distance() {
+        assert_eq!(0, edit_distance("count", "count"));
+        assert_eq!(1, edit_distance("countr", "counter"));
+        assert_eq!(3, edit_distance("kitten", "sitting"));
+    }
+
+    #[test]
+    fn can_suggest_closest_name() {
+        let candidates = ["counter", "position", "length"];
+        assert_eq!(
+            Some("counter"),
+            suggest("countr", candidates.iter().copied())
+        );
+        assert_eq!(None, suggest("x", candidates.iter().copied()));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod class;
+pub mod expression;
+pub mod identifier;
+pub mod statement;
+pub mod type_name;
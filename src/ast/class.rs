@@ -0,0 +1,84 @@
+use std::fmt::{Display, Formatter};
+
+use crate::ast::identifier::Identifier;
+use crate::ast::statement::Statement;
+use crate::ast::type_name::TypeName;
+use crate::tokenizer::span::Span;
+
+/// class = ’class’ className ’{’ classVarDec* subroutineDec* ’}’
+#[derive(Debug, Clone, PartialEq)]
+pub struct Class {
+    pub name: Identifier,
+    pub class_var_decs: Vec<ClassVarDec>,
+    pub subroutine_decs: Vec<SubroutineDec>,
+    pub span: Span,
+}
+
+/// classVarDec = (’static’ | ’field’) type varName (’,’ varName)* ’;’
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassVarDec {
+    pub kind: ClassVarKind,
+    pub type_name: TypeName,
+    pub var_names: Vec<Identifier>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClassVarKind {
+    Static,
+    Field,
+}
+
+impl Display for ClassVarKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", format!("{self:?}").to_lowercase())
+    }
+}
+
+/// subroutineDec = (’constructor’ | ’function’ | ’method’) (’void’ | type) subroutineName ’(’ parameterList ’)’ subroutineBody
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubroutineDec {
+    pub kind: SubroutineKind,
+    /// None if the subroutine is ’void’
+    pub return_type: Option<TypeName>,
+    pub name: Identifier,
+    pub parameters: Vec<Parameter>,
+    pub body: SubroutineBody,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SubroutineKind {
+    Constructor,
+    Function,
+    Method,
+}
+
+impl Display for SubroutineKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", format!("{self:?}").to_lowercase())
+    }
+}
+
+/// parameterList = ((type varName) (’,’ type varName)*)?
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    pub type_name: TypeName,
+    pub var_name: Identifier,
+}
+
+/// subroutineBody = ’{’ varDec* statements ’}’
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubroutineBody {
+    pub var_decs: Vec<VarDec>,
+    pub statements: Vec<Statement>,
+    pub span: Span,
+}
+
+/// varDec = ’var’ type varName (’,’ varName)* ’;’
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarDec {
+    pub type_name: TypeName,
+    pub var_names: Vec<Identifier>,
+    pub span: Span,
+}
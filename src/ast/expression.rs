@@ -0,0 +1,178 @@
+use crate::ast::identifier::Identifier;
+use crate::tokenizer::span::Span;
+
+/// expression = term (op term)*
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Term(Term),
+    /// Jack applies operators from left to right, so `a + b * c` has `a + b` as its lhs,
+    /// unless the usual precedence is chosen.
+    Binary(BinaryExpression),
+}
+
+impl Expression {
+    pub fn span(&self) -> Span {
+        match self {
+            Expression::Term(term) => term.span(),
+            Expression::Binary(binary) => binary.span,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinaryExpression {
+    pub op: BinaryOp,
+    pub op_span: Span,
+    pub lhs: Box<Expression>,
+    pub rhs: Box<Expression>,
+    pub span: Span,
+}
+
+/// op = ’+’ | ’-’ | ’*’ | ’/’ | ’&’ | ’|’ | ’<’ | ’>’ | ’=’ | ’&&’ | ’||’
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    And,
+    Or,
+    Lt,
+    Gt,
+    Eq,
+    /// `&&`, which skips its rhs when the lhs is false, only in the extended dialect
+    LogicalAnd,
+    /// `||`, which skips its rhs when the lhs is true, only in the extended dialect
+    LogicalOr,
+}
+
+impl BinaryOp {
+    pub fn from(symbol: &str) -> Option<Self> {
+        match symbol {
+            "+" => Some(BinaryOp::Add),
+            "-" => Some(BinaryOp::Sub),
+            "*" => Some(BinaryOp::Mul),
+            "/" => Some(BinaryOp::Div),
+            "&" => Some(BinaryOp::And),
+            "|" => Some(BinaryOp::Or),
+            "<" => Some(BinaryOp::Lt),
+            ">" => Some(BinaryOp::Gt),
+            "=" => Some(BinaryOp::Eq),
+            "&&" => Some(BinaryOp::LogicalAnd),
+            "||" => Some(BinaryOp::LogicalOr),
+            _ => None,
+        }
+    }
+
+    /// Returns how tightly the operator binds under the usual precedence, higher binding tighter.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            BinaryOp::LogicalOr => 1,
+            BinaryOp::LogicalAnd => 2,
+            BinaryOp::Or => 3,
+            BinaryOp::And => 4,
+            BinaryOp::Lt | BinaryOp::Gt | BinaryOp::Eq => 5,
+            BinaryOp::Add | BinaryOp::Sub => 6,
+            BinaryOp::Mul | BinaryOp::Div => 7,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::And => "&",
+            BinaryOp::Or => "|",
+            BinaryOp::Lt => "<",
+            BinaryOp::Gt => ">",
+            BinaryOp::Eq => "=",
+            BinaryOp::LogicalAnd => "&&",
+            BinaryOp::LogicalOr => "||",
+        }
+    }
+}
+
+/// unaryOp = ’-’ | ’~’
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Neg,
+    Not,
+}
+
+impl UnaryOp {
+    pub fn symbol(&self) -> char {
+        match self {
+            UnaryOp::Neg => '-',
+            UnaryOp::Not => '~',
+        }
+    }
+}
+
+/// keywordConstant = ’true’ | ’false’ | ’null’ | ’this’
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordConstant {
+    True,
+    False,
+    Null,
+    This,
+}
+
+/// The largest integer constant, since integers are 16-bit two's-complement
+pub const MAX_INTEGER_CONSTANT: usize = 32767;
+
+/// term = integerConstant | stringConstant | keywordConstant | varName | varName ’[’ expression ’]’ | subroutineCall | ’(’ expression ’)’ | unaryOp term
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    IntegerConstant(usize, Span),
+    /// A hexadecimal or binary constant, only in the extended dialect.
+    /// It is a 16-bit pattern, so the values above 32767 are the negative numbers.
+    BitPattern(usize, Span),
+    StringConstant(String, Span),
+    /// The code of a character in the Hack character set, only in the extended dialect
+    CharConstant(usize, Span),
+    KeywordConstant(KeywordConstant, Span),
+    VarName(Identifier),
+    ArrayElement(Identifier, Box<Expression>, Span),
+    SubroutineCall(Box<SubroutineCall>),
+    Parenthesized(Box<Expression>, Span),
+    Unary(UnaryOp, Box<Term>, Span),
+}
+
+impl Term {
+    pub fn span(&self) -> Span {
+        match self {
+            Term::IntegerConstant(_, span)
+            | Term::BitPattern(_, span)
+            | Term::StringConstant(_, span)
+            | Term::CharConstant(_, span)
+            | Term::KeywordConstant(_, span)
+            | Term::ArrayElement(_, _, span)
+            | Term::Parenthesized(_, span)
+            | Term::Unary(_, _, span) => *span,
+            Term::VarName(identifier) => identifier.span,
+            Term::SubroutineCall(subroutine_call) => subroutine_call.span,
+        }
+    }
+
+    /// Returns true for `-32768`, whose magnitude is the only one out of range for an integer constant.
+    pub fn is_min_integer(&self) -> bool {
+        match self {
+            Term::Unary(UnaryOp::Neg, term, _) => {
+                matches!(**term, Term::IntegerConstant(value, _) if value == MAX_INTEGER_CONSTANT + 1)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// subroutineCall = subroutineName ’(’ expressionList ’)’ | (className | varName) ’.’ subroutineName ’(’ expressionList ’)’
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubroutineCall {
+    /// The class or variable name before ’.’, if any
+    pub receiver: Option<Identifier>,
+    pub subroutine_name: Identifier,
+    pub arguments: Vec<Expression>,
+    pub span: Span,
+}
@@ -0,0 +1,17 @@
+use crate::tokenizer::span::Span;
+
+/// A name written in the source, such as a class, subroutine or variable name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Identifier {
+    pub name: String,
+    pub span: Span,
+}
+
+impl Identifier {
+    pub fn new(name: &str, span: &Span) -> Self {
+        Identifier {
+            name: String::from(name),
+            span: *span,
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::ast::expression::{Expression, SubroutineCall};
+use crate::ast::identifier::Identifier;
+use crate::tokenizer::span::Span;
+
+/// statement = letStatement | ifStatement | whileStatement | doStatement | returnStatement
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    Let(LetStatement),
+    If(IfStatement),
+    While(WhileStatement),
+    Do(DoStatement),
+    Return(ReturnStatement),
+}
+
+/// letStatement = ’let’ varName (’[’ expression ’]’)? ’=’ expression ’;’
+#[derive(Debug, Clone, PartialEq)]
+pub struct LetStatement {
+    pub var_name: Identifier,
+    pub index: Option<Expression>,
+    pub value: Expression,
+    pub span: Span,
+}
+
+/// ifStatement = ’if’ ’(’ expression ’)’ ’{’ statements ’}’ (’else’ ’{’ statements ’}’)?
+#[derive(Debug, Clone, PartialEq)]
+pub struct IfStatement {
+    pub condition: Expression,
+    pub statements: Vec<Statement>,
+    pub else_statements: Option<Vec<Statement>>,
+    pub span: Span,
+}
+
+/// whileStatement = ’while’ ’(’ expression ’)’ ’{’ statements ’}’
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhileStatement {
+    pub condition: Expression,
+    pub statements: Vec<Statement>,
+    pub span: Span,
+}
+
+/// doStatement = ’do’ subroutineCall ’;’
+#[derive(Debug, Clone, PartialEq)]
+pub struct DoStatement {
+    pub subroutine_call: SubroutineCall,
+    pub span: Span,
+}
+
+/// returnStatement = ’return’ expression? ’;’
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReturnStatement {
+    pub value: Option<Expression>,
+    pub span: Span,
+}
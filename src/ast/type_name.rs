@@ -0,0 +1,32 @@
+use std::fmt::{Display, Formatter};
+
+/// type = ’int’ | ’char’ | ’boolean’ | className
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeName {
+    Int,
+    Char,
+    Boolean,
+    Class(String),
+}
+
+impl TypeName {
+    pub fn from(value: &str) -> Self {
+        match value {
+            "int" => TypeName::Int,
+            "char" => TypeName::Char,
+            "boolean" => TypeName::Boolean,
+            _ => TypeName::Class(String::from(value)),
+        }
+    }
+}
+
+impl Display for TypeName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeName::Int => write!(f, "int"),
+            TypeName::Char => write!(f, "char"),
+            TypeName::Boolean => write!(f, "boolean"),
+            TypeName::Class(name) => write!(f, "{name}"),
+        }
+    }
+}
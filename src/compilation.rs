@@ -1,12 +1,14 @@
 mod class_compiler;
 mod class_var_dec_compiler;
+pub mod compat;
 pub mod compilation_engine;
 mod do_statement_compiler;
-mod expression_compiler;
+pub mod expression_compiler;
 mod expression_list_compiler;
 mod if_statement_compiler;
 mod let_statement_compiler;
 mod parameter_list_compiler;
+pub mod program;
 mod return_statement_compiler;
 mod statement_compiler;
 mod statements_compiler;
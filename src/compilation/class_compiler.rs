@@ -1,71 +1,46 @@
-use std::io::Write;
-
 use anyhow::Result;
 
+use crate::ast::class::{Class, SubroutineKind};
 use crate::compilation::class_var_dec_compiler::ClassVarDecCompiler;
+use crate::compilation::compat::Compat;
 use crate::compilation::subroutine_dec_compiler::SubroutineDecCompiler;
 use crate::symbol_table::kind::Kind;
 use crate::symbol_table::symbol_tables::SymbolTables;
-use crate::tokenizer::jack_tokenizer::JackTokenizer;
-use crate::tokenizer::key_word::KeyWord;
+use crate::writer::vm_command::VmCommand;
 
 /// class = ’class’ className ’{’ classVarDec* subroutineDec* ’}’
 pub struct ClassCompiler {}
 
 impl ClassCompiler {
+    /// Returns the VM commands of each subroutine.
     pub fn compile(
-        tokenizer: &mut JackTokenizer,
+        class: &Class,
         symbol_tables: &mut SymbolTables,
-        written: &mut impl Write,
-    ) -> Result<()> {
-        // ’class’
-        tokenizer.advance()?;
-
+        compat: Option<Compat>,
+    ) -> Result<Vec<Vec<VmCommand>>> {
         // className
-        let class_name = {
-            tokenizer.advance()?;
-            String::from(tokenizer.identifier())
-        };
-        symbol_tables.class_name = String::from(&class_name);
-
-        // {
-        tokenizer.advance()?;
+        symbol_tables.class_name = String::from(&class.name.name);
 
         // classVarDec*
-        while Self::exist_class_var_dec(tokenizer)? {
-            ClassVarDecCompiler::compile(tokenizer, symbol_tables)?;
+        for class_var_dec in &class.class_var_decs {
+            ClassVarDecCompiler::compile(class_var_dec, symbol_tables)?;
         }
 
         // subroutineDec*
-        while KeyWord::exists(tokenizer.peek()?.value()) {
-            match KeyWord::from(tokenizer.peek()?.value())? {
-                KeyWord::Constructor | KeyWord::Function => {
-                    symbol_tables.start_subroutine();
-                    SubroutineDecCompiler::compile(tokenizer, symbol_tables, written)?;
-                }
-                KeyWord::Method => {
-                    symbol_tables.start_subroutine();
-                    symbol_tables.define("this", &class_name, &Kind::Argument);
-                    SubroutineDecCompiler::compile(tokenizer, symbol_tables, written)?;
-                }
-                _ => break,
+        let mut subroutines = Vec::new();
+        for subroutine_dec in &class.subroutine_decs {
+            symbol_tables.start_subroutine();
+            if subroutine_dec.kind == SubroutineKind::Method {
+                symbol_tables.define("this", &class.name.name, &Kind::Argument, &class.name.span);
             }
+            subroutines.push(SubroutineDecCompiler::compile(
+                subroutine_dec,
+                symbol_tables,
+                compat,
+            )?);
         }
 
-        // }
-        tokenizer.advance()?;
-
-        Ok(())
-    }
-
-    fn exist_class_var_dec(tokenizer: &JackTokenizer) -> Result<bool> {
-        if !KeyWord::exists(tokenizer.peek()?.value()) {
-            return Ok(false);
-        }
-        match KeyWord::from(tokenizer.peek()?.value())? {
-            KeyWord::Static | KeyWord::Field => Ok(true),
-            _ => Ok(false),
-        }
+        Ok(subroutines)
     }
 }
 
@@ -74,6 +49,8 @@ mod tests {
     use std::io::{Seek, Write};
 
     use crate::compilation::class_compiler::ClassCompiler;
+    use crate::parsing::class_parser::ClassParser;
+    use crate::parsing::precedence::Precedence;
     use crate::symbol_table::kind::Kind;
     use crate::symbol_table::symbol_tables::SymbolTables;
     use crate::tokenizer::jack_tokenizer::JackTokenizer;
@@ -87,12 +64,12 @@ mod tests {
         writeln!(src_file, "}}").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let class = ClassParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
 
-        let result = ClassCompiler::compile(&mut tokenizer, &mut symbol_tables, &mut output);
+        let result = ClassCompiler::compile(&class, &mut symbol_tables, None);
 
         assert!(result.is_ok());
         assert_eq!(&Kind::Argument, symbol_tables.kind_of("this").unwrap());
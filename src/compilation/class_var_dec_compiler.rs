@@ -1,41 +1,25 @@
 use anyhow::Result;
 
+use crate::ast::class::ClassVarDec;
 use crate::symbol_table::kind::Kind;
 use crate::symbol_table::symbol_tables::SymbolTables;
-use crate::tokenizer::jack_tokenizer::JackTokenizer;
 
 /// classVarDec = (’static’ | ’field’) type varName (’,’ varName)* ’;’
 pub struct ClassVarDecCompiler {}
 
 impl ClassVarDecCompiler {
-    pub fn compile(tokenizer: &mut JackTokenizer, symbol_tables: &mut SymbolTables) -> Result<()> {
+    pub fn compile(class_var_dec: &ClassVarDec, symbol_tables: &mut SymbolTables) -> Result<()> {
         // static or field
-        let kind = Kind::from_str(tokenizer.peek()?.value())?;
-        tokenizer.advance()?;
+        let kind = Kind::from_str(&class_var_dec.kind.to_string())?;
 
         // type
-        let type_name = String::from(tokenizer.peek()?.value());
-        tokenizer.advance()?;
+        let type_name = class_var_dec.type_name.to_string();
 
-        // varName
-        let var_name = String::from(tokenizer.peek()?.value());
-        symbol_tables.define(&var_name, &type_name, &kind);
-        tokenizer.advance()?;
-
-        // (’,’ varName)*
-        while tokenizer.peek()?.value() == "," {
-            // ,
-            tokenizer.advance()?;
-
-            // varName
-            let var_name = String::from(tokenizer.peek()?.value());
-            symbol_tables.define(&var_name, &type_name, &kind);
-            tokenizer.advance()?;
+        // varName (’,’ varName)*
+        for var_name in &class_var_dec.var_names {
+            symbol_tables.define(&var_name.name, &type_name, &kind, &var_name.span);
         }
 
-        // ;
-        tokenizer.advance()?;
-
         Ok(())
     }
 }
@@ -45,6 +29,7 @@ mod tests {
     use std::io::{Seek, Write};
 
     use crate::compilation::class_var_dec_compiler::ClassVarDecCompiler;
+    use crate::parsing::class_var_dec_parser::ClassVarDecParser;
     use crate::symbol_table::kind::Kind;
     use crate::symbol_table::symbol_tables::SymbolTables;
     use crate::tokenizer::jack_tokenizer::JackTokenizer;
@@ -57,9 +42,10 @@ mod tests {
         let path = src_file.path();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let class_var_dec = ClassVarDecParser::parse(&mut tokenizer).unwrap();
         let mut symbol_tables = SymbolTables::new();
 
-        let result = ClassVarDecCompiler::compile(&mut tokenizer, &mut symbol_tables);
+        let result = ClassVarDecCompiler::compile(&class_var_dec, &mut symbol_tables);
 
         assert!(result.is_ok());
         assert_eq!(2, symbol_tables.var_count(Kind::Static));
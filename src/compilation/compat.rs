@@ -0,0 +1,8 @@
+use clap::ValueEnum;
+
+/// Another Jack compiler whose VM code is matched instruction by instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Compat {
+    /// The JackCompiler of the nand2tetris tools
+    Reference,
+}
@@ -1,14 +1,31 @@
 use std::io::Write;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 
+use crate::analysis::checker::Checker;
+use crate::analysis::lint::LintLevels;
+use crate::analysis::registry::Registry;
+use crate::ast::class::Class;
 use crate::compilation::class_compiler::ClassCompiler;
+use crate::compilation::compat::Compat;
+use crate::error::diagnostics::Diagnostics;
+use crate::optimization::constant_folding::ConstantFolding;
+use crate::optimization::optimization_stats::OptimizationStats;
+use crate::optimization::peephole::Peephole;
+use crate::parsing::class_parser::ClassParser;
+use crate::parsing::precedence::Precedence;
 use crate::symbol_table::symbol_tables::SymbolTables;
 use crate::tokenizer::jack_tokenizer::JackTokenizer;
+use crate::writer::vm_writer::VmWriter;
 
 pub struct CompilationEngine {
     tokenizer: JackTokenizer,
     symbol_tables: SymbolTables,
+    lint_levels: LintLevels,
+    compat: Option<Compat>,
+    precedence: Precedence,
+    /// Present when the generated code is optimized
+    optimization_stats: Option<OptimizationStats>,
 }
 
 impl CompilationEngine {
@@ -16,11 +33,271 @@ impl CompilationEngine {
         CompilationEngine {
             tokenizer,
             symbol_tables: SymbolTables::new(),
+            lint_levels: LintLevels::default(),
+            compat: None,
+            precedence: Precedence::default(),
+            optimization_stats: None,
         }
     }
 
-    pub fn compile(&mut self, written: &mut impl Write) -> Result<()> {
-        ClassCompiler::compile(&mut self.tokenizer, &mut self.symbol_tables, written)?;
-        Ok(())
+    pub fn set_lint_levels(&mut self, lint_levels: LintLevels) {
+        self.lint_levels = lint_levels;
+    }
+
+    /// Makes the generated code match the output of another compiler.
+    pub fn set_compat(&mut self, compat: Option<Compat>) {
+        self.compat = compat;
+    }
+
+    /// Sets how operators without parentheses are grouped.
+    pub fn set_precedence(&mut self, precedence: Precedence) {
+        self.precedence = precedence;
+    }
+
+    /// Folds constant expressions and runs the peephole optimizer over the code of each subroutine.
+    pub fn set_optimize(&mut self, optimize: bool) {
+        self.optimization_stats = optimize.then(OptimizationStats::default);
+    }
+
+    pub fn file_name(&self) -> &str {
+        self.tokenizer.file_name()
+    }
+
+    /// Returns how much the code compiled so far was optimized, if it was.
+    pub fn optimization_stats(&self) -> Option<&OptimizationStats> {
+        self.optimization_stats.as_ref()
+    }
+
+    /// Parses the whole class, failing with all the syntax errors found in it.
+    pub fn parse(&mut self) -> Result<Class> {
+        let class = match ClassParser::parse(&mut self.tokenizer, self.precedence) {
+            Ok(class) => Some(class),
+            Err(error) => {
+                self.tokenizer.recover(error)?;
+                None
+            }
+        };
+
+        let diagnostics = self.tokenizer.take_diagnostics();
+        match class {
+            Some(class) if diagnostics.is_empty() => Ok(class),
+            _ => bail!(diagnostics),
+        }
+    }
+
+    /// Parses and checks the whole class first and only generates code when it has no errors.
+    /// Only calls within the class are checked, see `Program` to check calls between classes.
+    /// Returns the warnings found on the way.
+    #[cfg(test)]
+    pub fn compile(&mut self, written: &mut impl Write) -> Result<Diagnostics> {
+        let class = self.parse()?;
+        let mut registry = Registry::new();
+        registry.register(&class);
+        registry.register_os();
+        self.compile_class(&class, &registry, written)
+    }
+
+    /// Checks a parsed class against the signatures in the registry and generates its code when it has no errors.
+    /// Returns the warnings found on the way.
+    pub fn compile_class(
+        &mut self,
+        class: &Class,
+        registry: &Registry,
+        written: &mut impl Write,
+    ) -> Result<Diagnostics> {
+        let mut diagnostics = self.tokenizer.take_diagnostics();
+        Checker::check(
+            class,
+            registry,
+            self.tokenizer.file_name(),
+            &self.lint_levels,
+            &mut diagnostics,
+        );
+        if diagnostics.has_errors() {
+            bail!(diagnostics)
+        }
+
+        let subroutines = match &mut self.optimization_stats {
+            None => ClassCompiler::compile(class, &mut self.symbol_tables, self.compat)?,
+            Some(stats) => {
+                // the unoptimized code is only measured, so it gets symbol tables of its own
+                let unoptimized =
+                    ClassCompiler::compile(class, &mut SymbolTables::new(), self.compat)?;
+                stats.before += unoptimized.iter().map(Vec::len).sum::<usize>();
+
+                let mut class = class.clone();
+                ConstantFolding::fold_class(&mut class, stats);
+                let subroutines: Vec<_> =
+                    ClassCompiler::compile(&class, &mut self.symbol_tables, self.compat)?
+                        .into_iter()
+                        .map(|subroutine| Peephole::optimize(subroutine, stats))
+                        .collect();
+                stats.after += subroutines.iter().map(Vec::len).sum::<usize>();
+                subroutines
+            }
+        };
+
+        for subroutine in &subroutines {
+            VmWriter::write(subroutine, written)?;
+        }
+        Ok(diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::compilation::compilation_engine::CompilationEngine;
+    use crate::error::diagnostics::Diagnostics;
+    use crate::symbol_table::kind::Kind;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+    #[test]
+    fn can_report_all_syntax_errors() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Main {{").unwrap();
+        writeln!(src_file, "    field int x y;").unwrap();
+        writeln!(src_file, "    function void main() {{").unwrap();
+        writeln!(src_file, "        var int a;").unwrap();
+        writeln!(src_file, "        let a = 1").unwrap();
+        writeln!(src_file, "        while (a > ) {{").unwrap();
+        writeln!(src_file, "            let a = a - 1;").unwrap();
+        writeln!(src_file, "        }}").unwrap();
+        writeln!(src_file, "        return;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "    method void run() {{").unwrap();
+        writeln!(src_file, "        do run(;").unwrap();
+        writeln!(src_file, "        return;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+        let mut output = Vec::<u8>::new();
+
+        let mut engine = CompilationEngine::new(JackTokenizer::new(path).unwrap());
+
+        let result = engine.compile(&mut output);
+        let diagnostics = result.unwrap_err().downcast::<Diagnostics>().unwrap();
+        let actual: Vec<String> = diagnostics.iter().map(|d| d.message.clone()).collect();
+
+        assert_eq!(
+            vec![
+                "expected `;` after class variable declaration, found `y`",
+                "expected `;` after let statement, found `while`",
+                "expected expression, found `)`",
+                "expected `)` after argument list, found `;`",
+                "unexpected end of file",
+            ],
+            actual
+        );
+    }
+
+    #[test]
+    fn can_recover_from_missing_semicolon_after_return() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Main {{").unwrap();
+        writeln!(src_file, "    function void main() {{").unwrap();
+        writeln!(src_file, "        return").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "    function int run() {{").unwrap();
+        writeln!(src_file, "        let a = ;").unwrap();
+        writeln!(src_file, "        return 1;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+        let mut output = Vec::<u8>::new();
+
+        let mut engine = CompilationEngine::new(JackTokenizer::new(path).unwrap());
+
+        let result = engine.compile(&mut output);
+        let diagnostics = result.unwrap_err().downcast::<Diagnostics>().unwrap();
+        let actual: Vec<String> = diagnostics.iter().map(|d| d.message.clone()).collect();
+
+        assert_eq!(
+            vec![
+                "expected `;` after return statement, found `}`",
+                "expected expression, found `;`",
+            ],
+            actual
+        );
+    }
+
+    #[test]
+    fn can_compile_fields_and_statics_once_when_optimizing() {
+        let expected = "\
+function Point.new 0
+push constant 2
+call Memory.alloc 1
+pop pointer 0
+push constant 3
+pop this 0
+push this 0
+pop this 1
+push constant 8
+pop static 1
+push static 0
+push constant 1
+add
+pop static 0
+push pointer 0
+return
+";
+
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Point {{").unwrap();
+        writeln!(src_file, "    field int x, y;").unwrap();
+        writeln!(src_file, "    static int count, limit;").unwrap();
+        writeln!(src_file, "    constructor Point new() {{").unwrap();
+        writeln!(src_file, "        let x = 1 + 2;").unwrap();
+        writeln!(src_file, "        let y = x;").unwrap();
+        writeln!(src_file, "        let limit = 4 * 2;").unwrap();
+        writeln!(src_file, "        let count = count + 1;").unwrap();
+        writeln!(src_file, "        return this;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+        let mut output = Vec::<u8>::new();
+
+        let mut engine = CompilationEngine::new(JackTokenizer::new(path).unwrap());
+        engine.set_optimize(true);
+
+        let result = engine.compile(&mut output);
+        let actual = String::from_utf8(output).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(expected, actual);
+        assert_eq!(2, engine.symbol_tables.var_count(Kind::Field));
+        assert_eq!(2, engine.symbol_tables.var_count(Kind::Static));
+        assert_eq!(Some(1), engine.symbol_tables.index_of("y"));
+        assert_eq!(Some(1), engine.symbol_tables.index_of("limit"));
+        let stats = engine.optimization_stats().unwrap();
+        assert_eq!((20, 16), (stats.before, stats.after));
+    }
+
+    #[test]
+    fn can_stop_at_error_limit() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Main {{").unwrap();
+        writeln!(src_file, "    function void main() {{").unwrap();
+        writeln!(src_file, "        let a = ;").unwrap();
+        writeln!(src_file, "        let b = ;").unwrap();
+        writeln!(src_file, "        let c = ;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+        let mut output = Vec::<u8>::new();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        tokenizer.set_error_limit(2);
+        let mut engine = CompilationEngine::new(tokenizer);
+
+        let result = engine.compile(&mut output);
+        let actual = result.unwrap_err().downcast::<Diagnostics>().unwrap();
+
+        assert_eq!(2, actual.len());
+        assert!(actual.is_full());
     }
 }
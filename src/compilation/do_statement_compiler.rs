@@ -1,36 +1,38 @@
-use std::io::Write;
-
 use anyhow::Result;
 
+use crate::ast::statement::DoStatement;
+use crate::compilation::compat::Compat;
 use crate::compilation::subroutine_call_compiler::SubroutineCallCompiler;
 use crate::symbol_table::symbol_tables::SymbolTables;
-use crate::tokenizer::jack_tokenizer::JackTokenizer;
+use crate::writer::label::LabelCreator;
 use crate::writer::segment::Segment;
-use crate::writer::vm_writer::VmWriter;
+use crate::writer::vm_command::VmCommand;
 
 /// doStatement = ’do’ subroutineCall ’;’
 pub struct DoStatementCompiler {}
 
 impl DoStatementCompiler {
     pub fn compile(
-        tokenizer: &mut JackTokenizer,
+        do_statement: &DoStatement,
         symbol_tables: &mut SymbolTables,
-        written: &mut impl Write,
+        commands: &mut Vec<VmCommand>,
+        label_creator: &mut dyn LabelCreator,
+        compat: Option<Compat>,
     ) -> Result<()> {
-        // do
-        tokenizer.advance()?;
-
         // subroutineCall
-        SubroutineCallCompiler::compile(tokenizer, symbol_tables, written)?;
+        SubroutineCallCompiler::compile(
+            &do_statement.subroutine_call,
+            symbol_tables,
+            commands,
+            label_creator,
+            compat,
+        )?;
 
         // After the called function returns,
         // the caller's memory segments-argument, local, static, this, that, and pointer-are
         // the same as before the function call.
         // However, the temp segment is undefined, so it must be defined.
-        VmWriter::write_pop(&Segment::Temp, 0, written)?;
-
-        // ’;’
-        tokenizer.advance()?;
+        commands.push(VmCommand::Pop(Segment::Temp, 0));
 
         Ok(())
     }
@@ -1,48 +1,123 @@
-use std::io::Write;
-
 use anyhow::Result;
 
+use crate::ast::expression::{BinaryExpression, BinaryOp, Expression};
+use crate::compilation::compat::Compat;
 use crate::compilation::term_compiler::TermCompiler;
 use crate::symbol_table::symbol_tables::SymbolTables;
-use crate::tokenizer::jack_tokenizer::JackTokenizer;
 use crate::writer::command::Command;
-use crate::writer::vm_writer::VmWriter;
+use crate::writer::label::LabelCreator;
+use crate::writer::segment::Segment;
+use crate::writer::vm_command::VmCommand;
 
 /// expression = term (op term)*
 pub struct ExpressionCompiler {}
 
 impl ExpressionCompiler {
     pub fn compile(
-        tokenizer: &mut JackTokenizer,
+        expression: &Expression,
         symbol_tables: &mut SymbolTables,
-        written: &mut impl Write,
+        commands: &mut Vec<VmCommand>,
+        label_creator: &mut dyn LabelCreator,
+        compat: Option<Compat>,
     ) -> Result<()> {
-        // term
-        TermCompiler::compile(tokenizer, symbol_tables, written)?;
-
-        // (op term)*
-        while tokenizer.peek()?.is_op() {
-            // op
-            let op = {
-                tokenizer.advance()?;
-                tokenizer.symbol()
-            };
-
+        match expression {
             // term
-            TermCompiler::compile(tokenizer, symbol_tables, written)?;
-
-            if let Some(command) = Command::from(op) {
-                VmWriter::write_arithmetic(&command, written)?;
-            } else if op == '*' {
-                VmWriter::write_call("Math.multiply", 2, written)?;
-            } else {
-                // in case of '/'(divide)
-                VmWriter::write_call("Math.divide", 2, written)?;
+            Expression::Term(term) => {
+                TermCompiler::compile(term, symbol_tables, commands, label_creator, compat)?
+            }
+            // expression (’&&’ | ’||’) term
+            Expression::Binary(binary)
+                if matches!(binary.op, BinaryOp::LogicalAnd | BinaryOp::LogicalOr) =>
+            {
+                Self::compile_short_circuit(binary, symbol_tables, commands, label_creator, compat)?
+            }
+            // expression op term
+            Expression::Binary(binary) => {
+                ExpressionCompiler::compile(
+                    &binary.lhs,
+                    symbol_tables,
+                    commands,
+                    label_creator,
+                    compat,
+                )?;
+                ExpressionCompiler::compile(
+                    &binary.rhs,
+                    symbol_tables,
+                    commands,
+                    label_creator,
+                    compat,
+                )?;
+
+                if let Some(command) = Command::from(binary.op.symbol()) {
+                    commands.push(VmCommand::Arithmetic(command));
+                } else if binary.op == BinaryOp::Mul {
+                    commands.push(VmCommand::Call(String::from("Math.multiply"), 2));
+                } else {
+                    // in case of '/'(divide)
+                    commands.push(VmCommand::Call(String::from("Math.divide"), 2));
+                }
             }
         }
 
         Ok(())
     }
+
+    /// Evaluates the rhs only when the lhs doesn't decide the result, which is always -1 or 0.
+    fn compile_short_circuit(
+        binary: &BinaryExpression,
+        symbol_tables: &mut SymbolTables,
+        commands: &mut Vec<VmCommand>,
+        label_creator: &mut dyn LabelCreator,
+        compat: Option<Compat>,
+    ) -> Result<()> {
+        ExpressionCompiler::compile(&binary.lhs, symbol_tables, commands, label_creator, compat)?;
+
+        if binary.op == BinaryOp::LogicalAnd {
+            let index = label_creator.next_index("AND");
+            let label_rhs = format!("AND_RHS{index}");
+            let label_end = format!("AND_END{index}");
+            commands.push(VmCommand::IfGoto(label_rhs.clone()));
+            commands.push(VmCommand::Push(Segment::Constant, 0));
+            commands.push(VmCommand::Goto(label_end.clone()));
+            commands.push(VmCommand::Label(label_rhs));
+            ExpressionCompiler::compile(
+                &binary.rhs,
+                symbol_tables,
+                commands,
+                label_creator,
+                compat,
+            )?;
+            Self::compile_canonical_boolean(commands);
+            commands.push(VmCommand::Label(label_end));
+        } else {
+            let index = label_creator.next_index("OR");
+            let label_true = format!("OR_TRUE{index}");
+            let label_end = format!("OR_END{index}");
+            commands.push(VmCommand::IfGoto(label_true.clone()));
+            ExpressionCompiler::compile(
+                &binary.rhs,
+                symbol_tables,
+                commands,
+                label_creator,
+                compat,
+            )?;
+            Self::compile_canonical_boolean(commands);
+            commands.push(VmCommand::Goto(label_end.clone()));
+            commands.push(VmCommand::Label(label_true));
+            commands.push(VmCommand::Push(Segment::Constant, 0));
+            commands.push(VmCommand::Arithmetic(Command::Not));
+            commands.push(VmCommand::Label(label_end));
+        }
+
+        Ok(())
+    }
+
+    /// Turns any non-zero value on the stack into -1, as `if-goto` takes any of them as true.
+    fn compile_canonical_boolean(commands: &mut Vec<VmCommand>) {
+        commands.push(VmCommand::Push(Segment::Constant, 0));
+        commands.push(VmCommand::Arithmetic(Command::Eq));
+        commands.push(VmCommand::Arithmetic(Command::Not));
+    }
 }
 
 #[cfg(test)]
@@ -50,8 +125,16 @@ mod tests {
     use std::io::{Seek, Write};
 
     use crate::compilation::expression_compiler::ExpressionCompiler;
+    use crate::parsing::expression_parser::ExpressionParser;
+    use crate::parsing::precedence::Precedence;
+    use crate::symbol_table::kind::Kind;
     use crate::symbol_table::symbol_tables::SymbolTables;
+    use crate::tokenizer::dialect::Dialect;
     use crate::tokenizer::jack_tokenizer::JackTokenizer;
+    use crate::tokenizer::span::Span;
+    use crate::writer::label::CounterLabelCreator;
+    use crate::writer::vm_command::VmCommand;
+    use crate::writer::vm_writer::VmWriter;
 
     #[test]
     fn can_compile() {
@@ -69,12 +152,75 @@ add
         writeln!(src_file, ")").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
+        let mut commands = Vec::<VmCommand>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let expression = ExpressionParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
 
-        let result = ExpressionCompiler::compile(&mut tokenizer, &mut symbol_tables, &mut output);
+        let result = ExpressionCompiler::compile(
+            &expression,
+            &mut symbol_tables,
+            &mut commands,
+            &mut CounterLabelCreator::default(),
+            None,
+        );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
+        let actual = String::from_utf8(output).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_compile_short_circuit_operators() {
+        let expected = "\
+push local 0
+if-goto AND_RHS0
+push constant 0
+goto AND_END0
+label AND_RHS0
+push local 1
+if-goto OR_TRUE0
+push local 2
+push constant 0
+eq
+not
+goto OR_END0
+label OR_TRUE0
+push constant 0
+not
+label OR_END0
+push constant 0
+eq
+not
+label AND_END0
+"
+        .to_string();
+
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "a && (b || c);").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+        let mut commands = Vec::<VmCommand>::new();
+
+        let mut tokenizer = JackTokenizer::with_dialect(path, Dialect::Extended).unwrap();
+        let expression = ExpressionParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+        let mut symbol_tables = SymbolTables::new();
+        for name in ["a", "b", "c"] {
+            symbol_tables.define(name, "boolean", &Kind::Var, &Span::default());
+        }
+
+        let result = ExpressionCompiler::compile(
+            &expression,
+            &mut symbol_tables,
+            &mut commands,
+            &mut CounterLabelCreator::default(),
+            None,
+        );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
 
         assert!(result.is_ok());
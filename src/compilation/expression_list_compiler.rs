@@ -1,39 +1,33 @@
-use std::io::Write;
-
 use anyhow::Result;
 
+use crate::ast::expression::Expression;
+use crate::compilation::compat::Compat;
 use crate::compilation::expression_compiler::ExpressionCompiler;
 use crate::symbol_table::symbol_tables::SymbolTables;
-use crate::tokenizer::jack_tokenizer::JackTokenizer;
+use crate::writer::label::LabelCreator;
+use crate::writer::vm_command::VmCommand;
 
 /// expressionList = (expression (’,’ expression)* )?
 pub struct ExpressionListCompiler {}
 
 impl ExpressionListCompiler {
     pub fn compile(
-        tokenizer: &mut JackTokenizer,
+        expressions: &[Expression],
         symbol_tables: &mut SymbolTables,
-        written: &mut impl Write,
+        commands: &mut Vec<VmCommand>,
+        label_creator: &mut dyn LabelCreator,
+        compat: Option<Compat>,
     ) -> Result<usize> {
-        let mut expression_count = 0;
-
-        // (expression)?
-        if tokenizer.is_term()? {
-            // expression
-            ExpressionCompiler::compile(tokenizer, symbol_tables, written)?;
-            expression_count += 1;
-        }
-
-        // (’,’ expression)*
-        while tokenizer.peek()?.value() == "," {
-            // ’,’
-            tokenizer.advance()?;
-
-            // expression
-            ExpressionCompiler::compile(tokenizer, symbol_tables, written)?;
-            expression_count += 1;
+        for expression in expressions {
+            ExpressionCompiler::compile(
+                expression,
+                symbol_tables,
+                commands,
+                label_creator,
+                compat,
+            )?;
         }
 
-        Ok(expression_count)
+        Ok(expressions.len())
     }
 }
@@ -1,70 +1,72 @@
-use std::io::Write;
-
 use anyhow::Result;
 
+use crate::ast::statement::IfStatement;
+use crate::compilation::compat::Compat;
 use crate::compilation::expression_compiler::ExpressionCompiler;
 use crate::compilation::statements_compiler::StatementsCompiler;
 use crate::symbol_table::symbol_tables::SymbolTables;
-use crate::tokenizer::jack_tokenizer::JackTokenizer;
-use crate::tokenizer::key_word::KeyWord;
-use crate::tokenizer::token_type::TokenType::Keyword;
 use crate::writer::command::Command;
 use crate::writer::label::LabelCreator;
-use crate::writer::vm_writer::VmWriter;
+use crate::writer::vm_command::VmCommand;
 
 /// ifStatement = ’if’ ’(’ expression ’)’ ’{’ statements ’}’ (’else’ ’{’ statements ’}’)?
 pub struct IfStatementCompiler {}
 
 impl IfStatementCompiler {
     pub fn compile(
-        tokenizer: &mut JackTokenizer,
+        if_statement: &IfStatement,
         symbol_tables: &mut SymbolTables,
-        written: &mut impl Write,
-        label_creator: &dyn LabelCreator,
+        commands: &mut Vec<VmCommand>,
+        label_creator: &mut dyn LabelCreator,
+        compat: Option<Compat>,
     ) -> Result<()> {
-        // if
-        tokenizer.advance()?;
-
-        // ’(’
-        tokenizer.advance()?;
+        let index = label_creator.next_index("IF");
+        let label_false = format!("IF_FALSE{index}");
 
         // expression
-        ExpressionCompiler::compile(tokenizer, symbol_tables, written)?;
-        VmWriter::write_arithmetic(&Command::Not, written)?;
-
-        let label_if: String = label_creator.create("if");
-        VmWriter::write_if(&label_if, written)?;
-
-        // ’)’
-        tokenizer.advance()?;
-
-        // ’{’
-        tokenizer.advance()?;
+        ExpressionCompiler::compile(
+            &if_statement.condition,
+            symbol_tables,
+            commands,
+            label_creator,
+            compat,
+        )?;
+        if compat == Some(Compat::Reference) {
+            // jump over the else branch when the condition holds, instead of negating it
+            let label_true = format!("IF_TRUE{index}");
+            commands.push(VmCommand::IfGoto(label_true.clone()));
+            commands.push(VmCommand::Goto(label_false.clone()));
+            commands.push(VmCommand::Label(label_true));
+        } else {
+            commands.push(VmCommand::Arithmetic(Command::Not));
+            commands.push(VmCommand::IfGoto(label_false.clone()));
+        }
 
         // statements
-        StatementsCompiler::compile(tokenizer, symbol_tables, written)?;
-
-        // ’}’
-        tokenizer.advance()?;
+        StatementsCompiler::compile(
+            &if_statement.statements,
+            symbol_tables,
+            commands,
+            label_creator,
+            compat,
+        )?;
 
         // (’else’ ’{’ statements ’}’)?
-        if tokenizer.peek()?.token_type() == &Keyword
-            && KeyWord::from(tokenizer.peek()?.value())? == KeyWord::Else
-        {
-            let label_goto = label_creator.create("goto");
-            VmWriter::write_goto(&label_goto, written)?;
-            VmWriter::write_label(&label_if, written)?;
-            // else
-            tokenizer.advance()?;
-            // ’{’
-            tokenizer.advance()?;
+        if let Some(else_statements) = &if_statement.else_statements {
+            let label_end = format!("IF_END{index}");
+            commands.push(VmCommand::Goto(label_end.clone()));
+            commands.push(VmCommand::Label(label_false));
             // statements
-            StatementsCompiler::compile(tokenizer, symbol_tables, written)?;
-            // ’}’
-            tokenizer.advance()?;
-            VmWriter::write_label(&label_goto, written)?;
+            StatementsCompiler::compile(
+                else_statements,
+                symbol_tables,
+                commands,
+                label_creator,
+                compat,
+            )?;
+            commands.push(VmCommand::Label(label_end));
         } else {
-            VmWriter::write_label(&label_if, written)?;
+            commands.push(VmCommand::Label(label_false));
         }
 
         Ok(())
@@ -77,11 +79,17 @@ mod tests {
 
     use mockall::predicate::eq;
 
+    use crate::compilation::compat::Compat;
     use crate::compilation::if_statement_compiler::IfStatementCompiler;
+    use crate::parsing::if_statement_parser::IfStatementParser;
+    use crate::parsing::precedence::Precedence;
     use crate::symbol_table::kind::Kind;
     use crate::symbol_table::symbol_tables::SymbolTables;
     use crate::tokenizer::jack_tokenizer::JackTokenizer;
-    use crate::writer::label::MockLabelCreator;
+    use crate::tokenizer::span::Span;
+    use crate::writer::label::{CounterLabelCreator, MockLabelCreator};
+    use crate::writer::vm_command::VmCommand;
+    use crate::writer::vm_writer::VmWriter;
 
     #[test]
     fn can_compile_if_else() {
@@ -93,22 +101,22 @@ push constant 0
 eq
 not
 not
-if-goto if_L1
+if-goto IF_FALSE0
 push constant 8000
 push local 1
 add
 push constant 1
 call Memory.poke 2
 pop temp 0
-goto goto_L2
-label if_L1
+goto IF_END0
+label IF_FALSE0
 push constant 8000
 push local 1
 add
 push constant 0
 call Memory.poke 2
 pop temp 0
-label goto_L2
+label IF_END0
 ";
 
         let mut src_file = tempfile::NamedTempFile::new().unwrap();
@@ -120,31 +128,31 @@ label goto_L2
         writeln!(src_file, "}}").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
+        let mut commands = Vec::<VmCommand>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let if_statement = IfStatementParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("this", "Main", &Kind::Argument);
-        symbol_tables.define("value", "int", &Kind::Argument);
-        symbol_tables.define("mask", "int", &Kind::Var);
-        symbol_tables.define("position", "int", &Kind::Var);
+        symbol_tables.define("this", "Main", &Kind::Argument, &Span::default());
+        symbol_tables.define("value", "int", &Kind::Argument, &Span::default());
+        symbol_tables.define("mask", "int", &Kind::Var, &Span::default());
+        symbol_tables.define("position", "int", &Kind::Var, &Span::default());
 
         let mut mock_label_creator = MockLabelCreator::default();
         mock_label_creator
-            .expect_create()
-            .with(eq("if"))
-            .return_const(String::from("if_L1"));
-        mock_label_creator
-            .expect_create()
-            .with(eq("goto"))
-            .return_const(String::from("goto_L2"));
+            .expect_next_index()
+            .with(eq("IF"))
+            .return_const(0usize);
 
         let result = IfStatementCompiler::compile(
-            &mut tokenizer,
+            &if_statement,
             &mut symbol_tables,
-            &mut output,
-            &mock_label_creator,
+            &mut commands,
+            &mut mock_label_creator,
+            None,
         );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
 
         assert!(result.is_ok());
@@ -158,11 +166,11 @@ push this 1
 push constant 1
 eq
 not
-if-goto if_L1
+if-goto IF_FALSE3
 push this 0
 call Square.moveUp 1
 pop temp 0
-label if_L1
+label IF_FALSE3
 ";
 
         let mut src_file = tempfile::NamedTempFile::new().unwrap();
@@ -170,26 +178,79 @@ label if_L1
         writeln!(src_file, "return;").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
+        let mut commands = Vec::<VmCommand>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let if_statement = IfStatementParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("square", "Square", &Kind::Field);
-        symbol_tables.define("direction", "int", &Kind::Field);
-        symbol_tables.define("this", "SquareGame", &Kind::Argument);
+        symbol_tables.define("square", "Square", &Kind::Field, &Span::default());
+        symbol_tables.define("direction", "int", &Kind::Field, &Span::default());
+        symbol_tables.define("this", "SquareGame", &Kind::Argument, &Span::default());
 
         let mut mock_label_creator = MockLabelCreator::default();
         mock_label_creator
-            .expect_create()
-            .with(eq("if"))
-            .return_const(String::from("if_L1"));
+            .expect_next_index()
+            .with(eq("IF"))
+            .return_const(3usize);
+
+        let result = IfStatementCompiler::compile(
+            &if_statement,
+            &mut symbol_tables,
+            &mut commands,
+            &mut mock_label_creator,
+            None,
+        );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
+        let actual = String::from_utf8(output).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_compile_if_else_in_reference_compat() {
+        let expected = "\
+push local 0
+push constant 0
+not
+eq
+if-goto IF_TRUE0
+goto IF_FALSE0
+label IF_TRUE0
+push constant 1
+pop local 0
+goto IF_END0
+label IF_FALSE0
+push constant 0
+pop local 0
+label IF_END0
+";
+
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(
+            src_file,
+            "if (x = true) {{ let x = 1; }} else {{ let x = 0; }}"
+        )
+        .unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+        let mut commands = Vec::<VmCommand>::new();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let if_statement = IfStatementParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+        let mut symbol_tables = SymbolTables::new();
+        symbol_tables.define("x", "int", &Kind::Var, &Span::default());
 
         let result = IfStatementCompiler::compile(
-            &mut tokenizer,
+            &if_statement,
             &mut symbol_tables,
-            &mut output,
-            &mock_label_creator,
+            &mut commands,
+            &mut CounterLabelCreator::default(),
+            Some(Compat::Reference),
         );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
 
         assert!(result.is_ok());
@@ -1,70 +1,61 @@
-use std::io::Write;
-
 use anyhow::Result;
 
+use crate::ast::statement::LetStatement;
+use crate::compilation::compat::Compat;
 use crate::compilation::expression_compiler::ExpressionCompiler;
+use crate::compilation::term_compiler::TermCompiler;
 use crate::symbol_table::symbol_tables::SymbolTables;
-use crate::tokenizer::jack_tokenizer::JackTokenizer;
-use crate::writer::command::Command;
+use crate::writer::label::LabelCreator;
 use crate::writer::segment::Segment;
-use crate::writer::vm_writer::VmWriter;
+use crate::writer::vm_command::VmCommand;
 
 /// letStatement = ’let’ varName (’[’ expression ’]’)? ’=’ expression ’;’
 pub struct LetStatementCompiler {}
 
 impl LetStatementCompiler {
     pub fn compile(
-        tokenizer: &mut JackTokenizer,
+        let_statement: &LetStatement,
         symbol_tables: &mut SymbolTables,
-        written: &mut impl Write,
+        commands: &mut Vec<VmCommand>,
+        label_creator: &mut dyn LabelCreator,
+        compat: Option<Compat>,
     ) -> Result<()> {
-        // let
-        tokenizer.advance()?;
-
         // varName
-        tokenizer.advance()?;
-        let var_name = String::from(tokenizer.identifier());
+        let var_name = &let_statement.var_name.name;
 
         // (’[’ expression ’]’)?
-        let mut is_array = false;
-        if tokenizer.peek()?.value() == "[" {
-            is_array = true;
-
-            if let Some(symbol) = symbol_tables.get(&var_name) {
-                VmWriter::write_push(&Segment::from(&symbol.kind), symbol.index, written)?;
-            }
-
-            // ’[’
-            tokenizer.advance()?;
-            // expression
-            ExpressionCompiler::compile(tokenizer, symbol_tables, written)?;
-            // ’]’
-            tokenizer.advance()?;
-
-            // add base address and index
-            VmWriter::write_arithmetic(&Command::Add, written)?;
+        if let Some(index) = &let_statement.index {
+            TermCompiler::compile_element_address(
+                &let_statement.var_name,
+                index,
+                symbol_tables,
+                commands,
+                label_creator,
+                compat,
+            )?;
         }
 
-        // ’=’
-        tokenizer.advance()?;
-
         // expression
-        ExpressionCompiler::compile(tokenizer, symbol_tables, written)?;
-
-        if is_array {
+        ExpressionCompiler::compile(
+            &let_statement.value,
+            symbol_tables,
+            commands,
+            label_creator,
+            compat,
+        )?;
+
+        if let_statement.index.is_some() {
             // Set the that segment to point to the address of an array element (using "pointer 1")
-            VmWriter::write_pop(&Segment::Temp, 0, written)?;
-            VmWriter::write_pop(&Segment::Pointer, 1, written)?;
+            commands.push(VmCommand::Pop(Segment::Temp, 0));
+            commands.push(VmCommand::Pop(Segment::Pointer, 1));
             // and access that array element using a "that 0" reference.
-            VmWriter::write_push(&Segment::Temp, 0, written)?;
-            VmWriter::write_pop(&Segment::That, 0, written)?;
-        } else if let Some(symbol) = symbol_tables.get(&var_name) {
-            VmWriter::write_pop(&Segment::from(&symbol.kind), symbol.index, written)?;
+            commands.push(VmCommand::Push(Segment::Temp, 0));
+            commands.push(VmCommand::Pop(Segment::That, 0));
+        } else {
+            let symbol = symbol_tables.resolve(var_name)?;
+            commands.push(VmCommand::Pop(Segment::from(&symbol.kind), symbol.index));
         }
 
-        // ’;’
-        tokenizer.advance()?;
-
         Ok(())
     }
 }
@@ -73,10 +64,17 @@ impl LetStatementCompiler {
 mod tests {
     use std::io::{Seek, Write};
 
+    use crate::compilation::compat::Compat;
     use crate::compilation::let_statement_compiler::LetStatementCompiler;
+    use crate::parsing::let_statement_parser::LetStatementParser;
+    use crate::parsing::precedence::Precedence;
     use crate::symbol_table::kind::Kind;
     use crate::symbol_table::symbol_tables::SymbolTables;
     use crate::tokenizer::jack_tokenizer::JackTokenizer;
+    use crate::tokenizer::span::Span;
+    use crate::writer::label::CounterLabelCreator;
+    use crate::writer::vm_command::VmCommand;
+    use crate::writer::vm_writer::VmWriter;
 
     #[test]
     fn can_compile() {
@@ -90,13 +88,22 @@ pop local 0
         writeln!(src_file, "let value = Memory.peek(8000);").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
+        let mut commands = Vec::<VmCommand>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let let_statement = LetStatementParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("value", "int", &Kind::Var);
-
-        let result = LetStatementCompiler::compile(&mut tokenizer, &mut symbol_tables, &mut output);
+        symbol_tables.define("value", "int", &Kind::Var, &Span::default());
+
+        let result = LetStatementCompiler::compile(
+            &let_statement,
+            &mut symbol_tables,
+            &mut commands,
+            &mut CounterLabelCreator::default(),
+            None,
+        );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
 
         assert!(result.is_ok());
@@ -120,15 +127,70 @@ pop local 2
         writeln!(src_file, "let sum = sum + a[i];").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
+        let mut commands = Vec::<VmCommand>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let let_statement = LetStatementParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("a", "Array", &Kind::Var);
-        symbol_tables.define("i", "int", &Kind::Var);
-        symbol_tables.define("sum", "int", &Kind::Var);
+        symbol_tables.define("a", "Array", &Kind::Var, &Span::default());
+        symbol_tables.define("i", "int", &Kind::Var, &Span::default());
+        symbol_tables.define("sum", "int", &Kind::Var, &Span::default());
+
+        let result = LetStatementCompiler::compile(
+            &let_statement,
+            &mut symbol_tables,
+            &mut commands,
+            &mut CounterLabelCreator::default(),
+            None,
+        );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
+        let actual = String::from_utf8(output).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_compile_array_in_reference_compat() {
+        let expected = "\
+push local 1
+push local 0
+add
+push local 1
+push local 0
+add
+pop pointer 1
+push that 0
+push constant 1
+add
+pop temp 0
+pop pointer 1
+push temp 0
+pop that 0
+";
 
-        let result = LetStatementCompiler::compile(&mut tokenizer, &mut symbol_tables, &mut output);
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "let a[i] = a[i] + 1;").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+        let mut commands = Vec::<VmCommand>::new();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let let_statement = LetStatementParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+        let mut symbol_tables = SymbolTables::new();
+        symbol_tables.define("a", "Array", &Kind::Var, &Span::default());
+        symbol_tables.define("i", "int", &Kind::Var, &Span::default());
+
+        let result = LetStatementCompiler::compile(
+            &let_statement,
+            &mut symbol_tables,
+            &mut commands,
+            &mut CounterLabelCreator::default(),
+            Some(Compat::Reference),
+        );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
 
         assert!(result.is_ok());
@@ -1,38 +1,21 @@
 use anyhow::Result;
 
+use crate::ast::class::Parameter;
 use crate::symbol_table::kind::Kind;
 use crate::symbol_table::symbol_tables::SymbolTables;
-use crate::tokenizer::jack_tokenizer::JackTokenizer;
 
 pub struct ParameterListCompiler {}
 
 impl ParameterListCompiler {
-    pub fn compile(tokenizer: &mut JackTokenizer, symbol_tables: &mut SymbolTables) -> Result<()> {
-        // (type varName)?
-        if tokenizer.peek()?.is_type()? {
-            // type
-            let type_name = String::from(tokenizer.peek()?.value());
-            tokenizer.advance()?;
-
-            // varName
-            let var_name = String::from(tokenizer.peek()?.value());
-            symbol_tables.define(&var_name, &type_name, &Kind::Argument);
-            tokenizer.advance()?;
-        }
-
-        // (’,’ type varName)*
-        while tokenizer.peek()?.value() == "," {
-            // ’,’
-            tokenizer.advance()?;
-
-            // type
-            let type_name = String::from(tokenizer.peek()?.value());
-            tokenizer.advance()?;
-
-            // varName
-            let var_name = String::from(tokenizer.peek()?.value());
-            symbol_tables.define(&var_name, &type_name, &Kind::Argument);
-            tokenizer.advance()?;
+    pub fn compile(parameters: &[Parameter], symbol_tables: &mut SymbolTables) -> Result<()> {
+        // ((type varName) (’,’ type varName)*)?
+        for parameter in parameters {
+            symbol_tables.define(
+                &parameter.var_name.name,
+                &parameter.type_name.to_string(),
+                &Kind::Argument,
+                &parameter.var_name.span,
+            );
         }
         Ok(())
     }
@@ -43,6 +26,7 @@ mod tests {
     use std::io::{Seek, Write};
 
     use crate::compilation::parameter_list_compiler::ParameterListCompiler;
+    use crate::parsing::parameter_list_parser::ParameterListParser;
     use crate::symbol_table::kind::Kind;
     use crate::symbol_table::symbol_tables::SymbolTables;
     use crate::tokenizer::jack_tokenizer::JackTokenizer;
@@ -55,9 +39,10 @@ mod tests {
         let path = src_file.path();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let parameters = ParameterListParser::parse(&mut tokenizer).unwrap();
         let mut symbol_tables = SymbolTables::new();
 
-        let result = ParameterListCompiler::compile(&mut tokenizer, &mut symbol_tables);
+        let result = ParameterListCompiler::compile(&parameters, &mut symbol_tables);
 
         assert!(result.is_ok());
         assert_eq!(3, symbol_tables.var_count(Kind::Argument));
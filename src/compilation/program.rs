@@ -0,0 +1,326 @@
+use anyhow::{bail, Result};
+
+use crate::analysis::lint::LintLevels;
+use crate::analysis::registry::Registry;
+use crate::ast::class::Class;
+use crate::compilation::compat::Compat;
+use crate::compilation::compilation_engine::CompilationEngine;
+use crate::error::diagnostic::Diagnostic;
+use crate::error::diagnostics::{Diagnostics, DEFAULT_ERROR_LIMIT};
+use crate::optimization::optimization_stats::OptimizationStats;
+use crate::parsing::precedence::Precedence;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+/// All the classes of a source compiled together, so calls between them can be checked.
+pub struct Program {
+    engines: Vec<CompilationEngine>,
+    siblings: Vec<CompilationEngine>,
+    error_limit: usize,
+}
+
+impl Program {
+    pub fn new(
+        tokenizers: Vec<JackTokenizer>,
+        lint_levels: &LintLevels,
+        compat: Option<Compat>,
+        optimize: bool,
+        precedence: Precedence,
+    ) -> Self {
+        let engines = tokenizers
+            .into_iter()
+            .map(|tokenizer| {
+                let mut engine = CompilationEngine::new(tokenizer);
+                engine.set_lint_levels(lint_levels.clone());
+                engine.set_compat(compat);
+                engine.set_optimize(optimize);
+                engine.set_precedence(precedence);
+                engine
+            })
+            .collect();
+        Program {
+            engines,
+            siblings: Vec::new(),
+            error_limit: DEFAULT_ERROR_LIMIT,
+        }
+    }
+
+    /// Sets the number of errors reported for a file by the program, like a duplicate class.
+    pub fn set_error_limit(&mut self, limit: usize) {
+        self.error_limit = limit;
+    }
+
+    /// Sets classes which are not compiled but can be called, like the other files next to a single source file.
+    pub fn set_siblings(&mut self, tokenizers: Vec<JackTokenizer>, precedence: Precedence) {
+        self.siblings = tokenizers
+            .into_iter()
+            .map(|tokenizer| {
+                let mut engine = CompilationEngine::new(tokenizer);
+                engine.set_precedence(precedence);
+                engine
+            })
+            .collect();
+    }
+
+    /// Parses every class first to collect their signatures, then checks and compiles each class against them.
+    /// Returns the VM code and warnings of each class, in the order of the tokenizers.
+    pub fn compile(&mut self) -> Vec<Result<(Vec<u8>, Diagnostics)>> {
+        let classes: Vec<_> = self.engines.iter_mut().map(|e| e.parse()).collect();
+
+        // a class with syntax errors has no signatures, so calls into it are not checked
+        let mut registry = Registry::new();
+        let mut duplicates = Vec::new();
+        for (index, class) in classes.iter().enumerate() {
+            let duplicate = match class {
+                Ok(class) if !registry.register(class) => {
+                    Some(self.report_duplicate(&classes[..index], index, class))
+                }
+                _ => None,
+            };
+            duplicates.push(duplicate);
+        }
+        // a sibling only adds its signatures, and a compiled class of the same name wins
+        for sibling in self.siblings.iter_mut() {
+            if let Ok(class) = sibling.parse() {
+                registry.register(&class);
+            }
+        }
+        registry.register_os();
+
+        let error_limit = self.error_limit;
+        self.engines
+            .iter_mut()
+            .zip(classes)
+            .zip(duplicates)
+            .map(|((engine, class), duplicate)| {
+                if let Some(diagnostic) = duplicate {
+                    let mut diagnostics = Diagnostics::new(error_limit);
+                    diagnostics.push(diagnostic);
+                    bail!(diagnostics)
+                }
+                let mut output = Vec::<u8>::new();
+                let warnings = engine.compile_class(&class?, &registry, &mut output)?;
+                Ok((output, warnings))
+            })
+            .collect()
+    }
+
+    /// Reports a class declared again by the file at `index`, pointing at the earlier declaration.
+    fn report_duplicate(
+        &self,
+        previous: &[Result<Class>],
+        index: usize,
+        class: &Class,
+    ) -> Diagnostic {
+        let name = &class.name.name;
+        let mut diagnostic = Diagnostic::new(
+            &format!("duplicate class `{name}`"),
+            self.engines[index].file_name(),
+            &class.name.span,
+        );
+        let first = previous
+            .iter()
+            .zip(&self.engines)
+            .find_map(|(other, engine)| match other {
+                Ok(other) if &other.name.name == name => {
+                    Some((engine.file_name(), other.name.span))
+                }
+                _ => None,
+            });
+        if let Some((file_name, span)) = first {
+            diagnostic = diagnostic.with_note_in(
+                &format!("`{name}` is first declared here"),
+                file_name,
+                &span,
+            );
+        }
+        diagnostic
+    }
+
+    /// Returns how much the code of all classes was optimized, if it was.
+    pub fn optimization_stats(&self) -> Option<OptimizationStats> {
+        let mut total: Option<OptimizationStats> = None;
+        for stats in self.engines.iter().filter_map(|e| e.optimization_stats()) {
+            total
+                .get_or_insert_with(OptimizationStats::default)
+                .add(stats);
+        }
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::analysis::lint::LintLevels;
+    use crate::compilation::program::Program;
+    use crate::error::diagnostics::Diagnostics;
+    use crate::parsing::precedence::Precedence;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+    #[test]
+    fn can_check_calls_between_classes() {
+        let mut main_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(main_file, "class Main {{").unwrap();
+        writeln!(main_file, "    function void main() {{").unwrap();
+        writeln!(main_file, "        var Point p;").unwrap();
+        writeln!(main_file, "        let p = Point.new(1);").unwrap();
+        writeln!(main_file, "        do p.mov(2, 3);").unwrap();
+        writeln!(main_file, "        do Point.move(2, 3);").unwrap();
+        writeln!(main_file, "        do p.new(1, 2);").unwrap();
+        writeln!(main_file, "        do Outptu.printInt(1);").unwrap();
+        writeln!(
+            main_file,
+            "        do Output.printInt(p.x());
+        do Output.printString(p.x());"
+        )
+        .unwrap();
+        writeln!(main_file, "        return;").unwrap();
+        writeln!(main_file, "    }}").unwrap();
+        writeln!(main_file, "}}").unwrap();
+        main_file.rewind().unwrap();
+
+        let mut point_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(point_file, "class Point {{").unwrap();
+        writeln!(point_file, "    field int x, y;").unwrap();
+        writeln!(point_file, "    constructor Point new(int ax, int ay) {{").unwrap();
+        writeln!(point_file, "        let x = ax;").unwrap();
+        writeln!(point_file, "        let y = ay;").unwrap();
+        writeln!(point_file, "        return this;").unwrap();
+        writeln!(point_file, "    }}").unwrap();
+        writeln!(point_file, "    method void move(int dx, int dy) {{").unwrap();
+        writeln!(point_file, "        let x = x + dx;").unwrap();
+        writeln!(point_file, "        let y = y + dy;").unwrap();
+        writeln!(point_file, "        return;").unwrap();
+        writeln!(point_file, "    }}").unwrap();
+        writeln!(point_file, "    method int x() {{").unwrap();
+        writeln!(point_file, "        return x;").unwrap();
+        writeln!(point_file, "    }}").unwrap();
+        writeln!(point_file, "}}").unwrap();
+        point_file.rewind().unwrap();
+
+        let tokenizers = vec![
+            JackTokenizer::new(main_file.path()).unwrap(),
+            JackTokenizer::new(point_file.path()).unwrap(),
+        ];
+        let mut program = Program::new(
+            tokenizers,
+            &LintLevels::default(),
+            None,
+            false,
+            Precedence::Jack,
+        );
+
+        let mut results = program.compile().into_iter();
+        let main = results.next().unwrap();
+        let point = results.next().unwrap();
+        let diagnostics = main.unwrap_err().downcast::<Diagnostics>().unwrap();
+        let actual: Vec<String> = diagnostics.iter().map(|d| d.message.clone()).collect();
+
+        assert_eq!(
+            vec![
+                "`Point.new` takes 2 argument(s) but 1 were given",
+                "no subroutine `mov` in class `Point`",
+                "method `Point.move` must be called on an object",
+                "`Point.new` is a constructor, not a method",
+                "no class `Outptu`",
+                "mismatched types: expected `String`, found `int`",
+            ],
+            actual
+        );
+        assert_eq!(
+            Some("did you mean `move`?"),
+            diagnostics.iter().nth(1).unwrap().help.as_deref()
+        );
+        assert_eq!(
+            Some("did you mean `Output`?"),
+            diagnostics.iter().nth(4).unwrap().help.as_deref()
+        );
+        assert!(point.is_ok());
+    }
+
+    #[test]
+    fn can_call_classes_of_siblings() {
+        let mut main_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(main_file, "class Main {{").unwrap();
+        writeln!(main_file, "    function void main() {{").unwrap();
+        writeln!(main_file, "        var Point p;").unwrap();
+        writeln!(main_file, "        let p = Point.new(1, 2);").unwrap();
+        writeln!(main_file, "        return;").unwrap();
+        writeln!(main_file, "    }}").unwrap();
+        writeln!(main_file, "}}").unwrap();
+        main_file.rewind().unwrap();
+
+        let mut point_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(point_file, "class Point {{").unwrap();
+        writeln!(point_file, "    constructor Point new(int ax, int ay) {{").unwrap();
+        writeln!(point_file, "        return this;").unwrap();
+        writeln!(point_file, "    }}").unwrap();
+        writeln!(point_file, "}}").unwrap();
+        point_file.rewind().unwrap();
+
+        let mut program = Program::new(
+            vec![JackTokenizer::new(main_file.path()).unwrap()],
+            &LintLevels::default(),
+            None,
+            false,
+            Precedence::Jack,
+        );
+        program.set_siblings(
+            vec![JackTokenizer::new(point_file.path()).unwrap()],
+            Precedence::Jack,
+        );
+
+        let results = program.compile();
+        let (output, warnings) = results.into_iter().next().unwrap().unwrap();
+
+        assert!(String::from_utf8(output)
+            .unwrap()
+            .contains("call Point.new 2\n"));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn can_report_duplicate_classes() {
+        let mut first_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(first_file, "class Point {{").unwrap();
+        writeln!(first_file, "}}").unwrap();
+        first_file.rewind().unwrap();
+
+        let mut second_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(second_file, "// the same class again").unwrap();
+        writeln!(second_file, "class Point {{").unwrap();
+        writeln!(second_file, "}}").unwrap();
+        second_file.rewind().unwrap();
+
+        let tokenizers = vec![
+            JackTokenizer::new(first_file.path()).unwrap(),
+            JackTokenizer::new(second_file.path()).unwrap(),
+        ];
+        let mut program = Program::new(
+            tokenizers,
+            &LintLevels::default(),
+            None,
+            false,
+            Precedence::Jack,
+        );
+        program.set_error_limit(1);
+
+        let mut results = program.compile().into_iter();
+        let first = results.next().unwrap();
+        let second = results.next().unwrap();
+        let diagnostics = second.unwrap_err().downcast::<Diagnostics>().unwrap();
+        let actual: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+
+        assert!(first.is_ok());
+        assert!(diagnostics.is_full());
+        assert_eq!(
+            vec![format!(
+                "duplicate class `Point` at {}:2:7\n  = note: `Point` is first declared here at {}:1:7",
+                second_file.path().display(),
+                first_file.path().display()
+            )],
+            actual
+        );
+    }
+}
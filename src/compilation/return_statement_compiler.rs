@@ -1,36 +1,32 @@
-use std::io::Write;
-
 use anyhow::Result;
 
+use crate::ast::statement::ReturnStatement;
+use crate::compilation::compat::Compat;
 use crate::compilation::expression_compiler::ExpressionCompiler;
 use crate::symbol_table::symbol_tables::SymbolTables;
-use crate::tokenizer::jack_tokenizer::JackTokenizer;
+use crate::writer::label::LabelCreator;
 use crate::writer::segment::Segment;
-use crate::writer::vm_writer::VmWriter;
+use crate::writer::vm_command::VmCommand;
 
 /// returnStatement = ’return’ expression? ’;’
 pub struct ReturnStatementCompiler {}
 
 impl ReturnStatementCompiler {
     pub fn compile(
-        tokenizer: &mut JackTokenizer,
+        return_statement: &ReturnStatement,
         symbol_tables: &mut SymbolTables,
-        written: &mut impl Write,
+        commands: &mut Vec<VmCommand>,
+        label_creator: &mut dyn LabelCreator,
+        compat: Option<Compat>,
     ) -> Result<()> {
-        // return
-        tokenizer.advance()?;
-
         // expression?
-        if tokenizer.peek()?.value() != ";" {
-            ExpressionCompiler::compile(tokenizer, symbol_tables, written)?;
+        if let Some(value) = &return_statement.value {
+            ExpressionCompiler::compile(value, symbol_tables, commands, label_creator, compat)?;
         } else {
-            VmWriter::write_push(&Segment::Constant, 0, written)?;
+            commands.push(VmCommand::Push(Segment::Constant, 0));
         }
 
-        // ’;’
-        tokenizer.advance()?;
-
-        VmWriter::write_return(written)?;
+        commands.push(VmCommand::Return);
         Ok(())
     }
 }
@@ -40,9 +36,15 @@ mod tests {
     use std::io::{Seek, Write};
 
     use crate::compilation::return_statement_compiler::ReturnStatementCompiler;
+    use crate::parsing::precedence::Precedence;
+    use crate::parsing::return_statement_parser::ReturnStatementParser;
     use crate::symbol_table::kind::Kind;
     use crate::symbol_table::symbol_tables::SymbolTables;
     use crate::tokenizer::jack_tokenizer::JackTokenizer;
+    use crate::tokenizer::span::Span;
+    use crate::writer::label::CounterLabelCreator;
+    use crate::writer::vm_command::VmCommand;
+    use crate::writer::vm_writer::VmWriter;
 
     #[test]
     fn can_compile() {
@@ -57,15 +59,24 @@ return
         writeln!(src_file, "return mask * 2;").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
+        let mut commands = Vec::<VmCommand>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let return_statement =
+            ReturnStatementParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("this", "Test", &Kind::Argument);
-        symbol_tables.define("mask", "int", &Kind::Argument);
-
-        let result =
-            ReturnStatementCompiler::compile(&mut tokenizer, &mut symbol_tables, &mut output);
+        symbol_tables.define("this", "Test", &Kind::Argument, &Span::default());
+        symbol_tables.define("mask", "int", &Kind::Argument, &Span::default());
+
+        let result = ReturnStatementCompiler::compile(
+            &return_statement,
+            &mut symbol_tables,
+            &mut commands,
+            &mut CounterLabelCreator::default(),
+            None,
+        );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
 
         assert!(result.is_ok());
@@ -83,13 +94,22 @@ return
         writeln!(src_file, "return;").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
+        let mut commands = Vec::<VmCommand>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let return_statement =
+            ReturnStatementParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
 
-        let result =
-            ReturnStatementCompiler::compile(&mut tokenizer, &mut symbol_tables, &mut output);
+        let result = ReturnStatementCompiler::compile(
+            &return_statement,
+            &mut symbol_tables,
+            &mut commands,
+            &mut CounterLabelCreator::default(),
+            None,
+        );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
 
         assert!(result.is_ok());
@@ -1,43 +1,63 @@
-use std::io::Write;
-
 use anyhow::Result;
 
+use crate::ast::statement::Statement;
+use crate::compilation::compat::Compat;
 use crate::compilation::do_statement_compiler::DoStatementCompiler;
 use crate::compilation::if_statement_compiler::IfStatementCompiler;
 use crate::compilation::let_statement_compiler::LetStatementCompiler;
 use crate::compilation::return_statement_compiler::ReturnStatementCompiler;
 use crate::compilation::while_statement_compiler::WhileStatementCompiler;
 use crate::symbol_table::symbol_tables::SymbolTables;
-use crate::tokenizer::jack_tokenizer::JackTokenizer;
-use crate::tokenizer::key_word::KeyWord;
-use crate::writer::label::RandomLabelCreator;
+use crate::writer::label::LabelCreator;
+use crate::writer::vm_command::VmCommand;
 
 /// statement = letStatement | ifStatement | whileStatement | doStatement | returnStatement
 pub struct StatementCompiler {}
 
 impl StatementCompiler {
     pub fn compile(
-        tokenizer: &mut JackTokenizer,
+        statement: &Statement,
         symbol_tables: &mut SymbolTables,
-        written: &mut impl Write,
+        commands: &mut Vec<VmCommand>,
+        label_creator: &mut dyn LabelCreator,
+        compat: Option<Compat>,
     ) -> Result<()> {
-        match KeyWord::from(tokenizer.peek()?.value())? {
-            KeyWord::Let => LetStatementCompiler::compile(tokenizer, symbol_tables, written)?,
-            KeyWord::If => IfStatementCompiler::compile(
-                tokenizer,
+        match statement {
+            Statement::Let(statement) => LetStatementCompiler::compile(
+                statement,
+                symbol_tables,
+                commands,
+                label_creator,
+                compat,
+            )?,
+            Statement::If(statement) => IfStatementCompiler::compile(
+                statement,
+                symbol_tables,
+                commands,
+                label_creator,
+                compat,
+            )?,
+            Statement::While(statement) => WhileStatementCompiler::compile(
+                statement,
+                symbol_tables,
+                commands,
+                label_creator,
+                compat,
+            )?,
+            Statement::Do(statement) => DoStatementCompiler::compile(
+                statement,
                 symbol_tables,
-                written,
-                &RandomLabelCreator::default(),
+                commands,
+                label_creator,
+                compat,
             )?,
-            KeyWord::While => WhileStatementCompiler::compile(
-                tokenizer,
+            Statement::Return(statement) => ReturnStatementCompiler::compile(
+                statement,
                 symbol_tables,
-                written,
-                &RandomLabelCreator::default(),
+                commands,
+                label_creator,
+                compat,
             )?,
-            KeyWord::Do => DoStatementCompiler::compile(tokenizer, symbol_tables, written)?,
-            KeyWord::Return => ReturnStatementCompiler::compile(tokenizer, symbol_tables, written)?,
-            _ => {}
         }
         Ok(())
     }
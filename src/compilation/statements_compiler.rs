@@ -1,28 +1,25 @@
-use std::io::Write;
-
 use anyhow::Result;
 
+use crate::ast::statement::Statement;
+use crate::compilation::compat::Compat;
 use crate::compilation::statement_compiler::StatementCompiler;
 use crate::symbol_table::symbol_tables::SymbolTables;
-use crate::tokenizer::jack_tokenizer::JackTokenizer;
-use crate::tokenizer::key_word::KeyWord;
+use crate::writer::label::LabelCreator;
+use crate::writer::vm_command::VmCommand;
 
 /// statements = statement*
 pub struct StatementsCompiler {}
 
 impl StatementsCompiler {
     pub fn compile(
-        tokenizer: &mut JackTokenizer,
+        statements: &[Statement],
         symbol_tables: &mut SymbolTables,
-        written: &mut impl Write,
+        commands: &mut Vec<VmCommand>,
+        label_creator: &mut dyn LabelCreator,
+        compat: Option<Compat>,
     ) -> Result<()> {
-        while KeyWord::exists(tokenizer.peek()?.value()) {
-            match KeyWord::from(tokenizer.peek()?.value())? {
-                KeyWord::Let | KeyWord::If | KeyWord::While | KeyWord::Do | KeyWord::Return => {
-                    StatementCompiler::compile(tokenizer, symbol_tables, written)?;
-                }
-                _ => break,
-            }
+        for statement in statements {
+            StatementCompiler::compile(statement, symbol_tables, commands, label_creator, compat)?;
         }
         Ok(())
     }
@@ -1,77 +1,73 @@
-use std::io::Write;
-
 use anyhow::Result;
 
+use crate::ast::class::{SubroutineBody, SubroutineKind};
+use crate::compilation::compat::Compat;
 use crate::compilation::statements_compiler::StatementsCompiler;
 use crate::compilation::var_dec_compiler::VarDecCompiler;
 use crate::symbol_table::kind::Kind;
 use crate::symbol_table::symbol_tables::SymbolTables;
-use crate::tokenizer::jack_tokenizer::JackTokenizer;
-use crate::tokenizer::key_word::KeyWord;
+use crate::writer::label::CounterLabelCreator;
 use crate::writer::segment::Segment;
-use crate::writer::vm_writer::VmWriter;
+use crate::writer::vm_command::VmCommand;
 
 /// subroutineBody = ’{’ varDec* statements ’}’
 pub struct SubroutineBodyCompiler {}
 
 impl SubroutineBodyCompiler {
     pub fn compile(
-        tokenizer: &mut JackTokenizer,
+        subroutine_body: &SubroutineBody,
         symbol_tables: &mut SymbolTables,
         subroutine_name: &str,
-        subroutine_type: &str,
-        written: &mut impl Write,
+        subroutine_kind: &SubroutineKind,
+        commands: &mut Vec<VmCommand>,
+        compat: Option<Compat>,
     ) -> Result<()> {
-        // ’{’
-        tokenizer.advance()?;
-
         // varDec*
-        while KeyWord::exists(tokenizer.peek()?.value())
-            && KeyWord::from(tokenizer.peek()?.value())? == KeyWord::Var
-        {
-            VarDecCompiler::compile(tokenizer, symbol_tables)?
+        for var_dec in &subroutine_body.var_decs {
+            VarDecCompiler::compile(var_dec, symbol_tables)?;
         }
 
-        VmWriter::write_function(
-            format!("{}.{}", symbol_tables.class_name, subroutine_name).as_str(),
+        commands.push(VmCommand::Function(
+            format!("{}.{}", symbol_tables.class_name, subroutine_name),
             symbol_tables.var_count(Kind::Var),
-            written,
-        )?;
+        ));
 
-        Self::set_pointer(symbol_tables, subroutine_type, written)?;
+        Self::set_pointer(symbol_tables, subroutine_kind, commands)?;
 
         // statements
-        StatementsCompiler::compile(tokenizer, symbol_tables, written)?;
-
-        // ’}’
-        tokenizer.advance()?;
+        StatementsCompiler::compile(
+            &subroutine_body.statements,
+            symbol_tables,
+            commands,
+            &mut CounterLabelCreator::default(),
+            compat,
+        )?;
 
         Ok(())
     }
 
     fn set_pointer(
         symbol_tables: &mut SymbolTables,
-        subroutine_type: &str,
-        written: &mut impl Write,
+        subroutine_kind: &SubroutineKind,
+        commands: &mut Vec<VmCommand>,
     ) -> Result<()> {
-        match subroutine_type {
-            "constructor" => {
-                VmWriter::write_push(
-                    &Segment::Constant,
+        match subroutine_kind {
+            SubroutineKind::Constructor => {
+                commands.push(VmCommand::Push(
+                    Segment::Constant,
                     symbol_tables.var_count(Kind::Field),
-                    written,
-                )?;
+                ));
                 // Allocate as much memory as the number of FIELD for the new object
-                VmWriter::write_call("Memory.alloc", 1, written)?;
+                commands.push(VmCommand::Call(String::from("Memory.alloc"), 1));
                 // Set this segment to point to the current object (constructor and method only)
-                VmWriter::write_pop(&Segment::Pointer, 0, written)?;
+                commands.push(VmCommand::Pop(Segment::Pointer, 0));
             }
-            "method" => {
-                VmWriter::write_push(&Segment::Argument, 0, written)?;
+            SubroutineKind::Method => {
+                commands.push(VmCommand::Push(Segment::Argument, 0));
                 // Set this segment to point to the current object (constructor and method only)
-                VmWriter::write_pop(&Segment::Pointer, 0, written)?;
+                commands.push(VmCommand::Pop(Segment::Pointer, 0));
             }
-            _ => {}
+            SubroutineKind::Function => {}
         }
 
         Ok(())
@@ -82,10 +78,16 @@ impl SubroutineBodyCompiler {
 mod tests {
     use std::io::{Seek, Write};
 
+    use crate::ast::class::SubroutineKind;
     use crate::compilation::subroutine_body_compiler::SubroutineBodyCompiler;
+    use crate::parsing::precedence::Precedence;
+    use crate::parsing::subroutine_body_parser::SubroutineBodyParser;
     use crate::symbol_table::kind::Kind;
     use crate::symbol_table::symbol_tables::SymbolTables;
     use crate::tokenizer::jack_tokenizer::JackTokenizer;
+    use crate::tokenizer::span::Span;
+    use crate::writer::vm_command::VmCommand;
+    use crate::writer::vm_writer::VmWriter;
 
     #[test]
     fn can_compile_function() {
@@ -101,19 +103,24 @@ function Test.convert 3
         writeln!(src_file, "}}").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
+        let mut commands = Vec::<VmCommand>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let subroutine_body =
+            SubroutineBodyParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
         symbol_tables.class_name = String::from("Test");
 
         let result = SubroutineBodyCompiler::compile(
-            &mut tokenizer,
+            &subroutine_body,
             &mut symbol_tables,
             "convert",
-            "function",
-            &mut output,
+            &SubroutineKind::Function,
+            &mut commands,
+            None,
         );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
 
         assert!(result.is_ok());
@@ -147,21 +154,26 @@ return
         writeln!(src_file, "}}").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
+        let mut commands = Vec::<VmCommand>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let subroutine_body =
+            SubroutineBodyParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
         symbol_tables.class_name = String::from("SquareGame");
-        symbol_tables.define("square", "Square", &Kind::Field);
-        symbol_tables.define("direction", "int", &Kind::Field);
+        symbol_tables.define("square", "Square", &Kind::Field, &Span::default());
+        symbol_tables.define("direction", "int", &Kind::Field, &Span::default());
 
         let result = SubroutineBodyCompiler::compile(
-            &mut tokenizer,
+            &subroutine_body,
             &mut symbol_tables,
             "new",
-            "constructor",
-            &mut output,
+            &SubroutineKind::Constructor,
+            &mut commands,
+            None,
         );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
 
         assert!(result.is_ok());
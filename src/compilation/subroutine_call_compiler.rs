@@ -1,69 +1,61 @@
-use std::io::Write;
-
 use anyhow::Result;
 
+use crate::ast::expression::SubroutineCall;
+use crate::compilation::compat::Compat;
 use crate::compilation::expression_list_compiler::ExpressionListCompiler;
 use crate::symbol_table::symbol_tables::SymbolTables;
-use crate::tokenizer::jack_tokenizer::JackTokenizer;
+use crate::writer::label::LabelCreator;
 use crate::writer::segment::Segment;
-use crate::writer::vm_writer::VmWriter;
+use crate::writer::vm_command::VmCommand;
 
 /// subroutineCall = subroutineName ’(’ expressionList ’)’ | (className | varName) ’.’ subroutineName ’(’ expressionList ’)’
 pub struct SubroutineCallCompiler {}
 
 impl SubroutineCallCompiler {
     pub fn compile(
-        tokenizer: &mut JackTokenizer,
+        subroutine_call: &SubroutineCall,
         symbol_tables: &mut SymbolTables,
-        written: &mut impl Write,
+        commands: &mut Vec<VmCommand>,
+        label_creator: &mut dyn LabelCreator,
+        compat: Option<Compat>,
     ) -> Result<()> {
         let mut number_of_args = 0;
+        let name = &subroutine_call.subroutine_name.name;
 
-        // subroutineName | (className | varName)
-        tokenizer.advance()?;
-        let subroutine_name = if tokenizer.peek()?.value() == "." {
-            let var_class_name = String::from(tokenizer.identifier());
+        // subroutineName | (className | varName) ’.’ subroutineName
+        let subroutine_name = if let Some(receiver) = &subroutine_call.receiver {
+            let var_class_name = &receiver.name;
 
-            if let Some(symbol) = symbol_tables.get(&var_class_name) {
-                VmWriter::write_push(&Segment::from(&symbol.kind), symbol.index, written)?;
+            if let Some(symbol) = symbol_tables.get(var_class_name) {
+                commands.push(VmCommand::Push(Segment::from(&symbol.kind), symbol.index));
                 number_of_args += 1;
             }
 
-            let class_name = if symbol_tables.type_of(&var_class_name).is_some() {
-                symbol_tables.type_of(&var_class_name).unwrap()
-            } else {
-                var_class_name
-            };
-
-            // ’.’
-            tokenizer.advance()?;
-
-            // subroutineName
-            tokenizer.advance()?;
-            let subroutine_name = String::from(tokenizer.identifier());
+            let class_name = symbol_tables
+                .type_of(var_class_name)
+                .unwrap_or_else(|| var_class_name.clone());
 
-            format!("{class_name}.{subroutine_name}")
+            format!("{class_name}.{name}")
         } else {
             // In the case of a method,
             // pass a reference to the object to which the method belongs as the first argument to be pushed.
-            VmWriter::write_push(&Segment::Pointer, 0, written)?;
+            commands.push(VmCommand::Push(Segment::Pointer, 0));
             number_of_args += 1;
 
             let class_name = String::from(&symbol_tables.class_name);
-            let subroutine_name = String::from(tokenizer.identifier());
-            format!("{class_name}.{subroutine_name}")
+            format!("{class_name}.{name}")
         };
 
-        // ’(’
-        tokenizer.advance()?;
-
         // expressionList
-        number_of_args += ExpressionListCompiler::compile(tokenizer, symbol_tables, written)?;
-
-        VmWriter::write_call(subroutine_name.as_str(), number_of_args, written)?;
+        number_of_args += ExpressionListCompiler::compile(
+            &subroutine_call.arguments,
+            symbol_tables,
+            commands,
+            label_creator,
+            compat,
+        )?;
 
-        // ’)’
-        tokenizer.advance()?;
+        commands.push(VmCommand::Call(subroutine_name, number_of_args));
 
         Ok(())
     }
@@ -74,8 +66,13 @@ mod tests {
     use std::io::{Seek, Write};
 
     use crate::compilation::subroutine_call_compiler::SubroutineCallCompiler;
+    use crate::parsing::precedence::Precedence;
+    use crate::parsing::subroutine_call_parser::SubroutineCallParser;
     use crate::symbol_table::symbol_tables::SymbolTables;
     use crate::tokenizer::jack_tokenizer::JackTokenizer;
+    use crate::writer::label::CounterLabelCreator;
+    use crate::writer::vm_command::VmCommand;
+    use crate::writer::vm_writer::VmWriter;
 
     #[test]
     fn can_compile() {
@@ -89,13 +86,22 @@ call Output.printInt 1
         writeln!(src_file, "Output.printInt(100)").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
+        let mut commands = Vec::<VmCommand>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let subroutine_call =
+            SubroutineCallParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
 
-        let result =
-            SubroutineCallCompiler::compile(&mut tokenizer, &mut symbol_tables, &mut output);
+        let result = SubroutineCallCompiler::compile(
+            &subroutine_call,
+            &mut symbol_tables,
+            &mut commands,
+            &mut CounterLabelCreator::default(),
+            None,
+        );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
 
         assert!(result.is_ok());
@@ -115,14 +121,23 @@ call Output.printInt 2
         writeln!(src_file, "printInt(100)").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
+        let mut commands = Vec::<VmCommand>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let subroutine_call =
+            SubroutineCallParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
         symbol_tables.class_name = String::from("Output");
 
-        let result =
-            SubroutineCallCompiler::compile(&mut tokenizer, &mut symbol_tables, &mut output);
+        let result = SubroutineCallCompiler::compile(
+            &subroutine_call,
+            &mut symbol_tables,
+            &mut commands,
+            &mut CounterLabelCreator::default(),
+            None,
+        );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
 
         assert!(result.is_ok());
@@ -1,52 +1,36 @@
-use std::io::Write;
-
 use anyhow::Result;
 
+use crate::ast::class::SubroutineDec;
+use crate::compilation::compat::Compat;
 use crate::compilation::parameter_list_compiler::ParameterListCompiler;
 use crate::compilation::subroutine_body_compiler::SubroutineBodyCompiler;
 use crate::symbol_table::symbol_tables::SymbolTables;
-use crate::tokenizer::jack_tokenizer::JackTokenizer;
+use crate::writer::vm_command::VmCommand;
 
 /// subroutineDec =(’constructor’ | ’function’ | ’method’) (’void’ | type) subroutineName ’(’ parameterList ’)’ subroutineBody
 pub struct SubroutineDecCompiler {}
 
 impl SubroutineDecCompiler {
     pub fn compile(
-        tokenizer: &mut JackTokenizer,
+        subroutine_dec: &SubroutineDec,
         symbol_tables: &mut SymbolTables,
-        written: &mut impl Write,
-    ) -> Result<()> {
-        // ’constructor’ | ’function’ | ’method’
-        let subroutine_type = {
-            tokenizer.advance()?;
-            String::from(tokenizer.identifier())
-        };
-
-        // ’void’ | type
-        tokenizer.advance()?;
-
-        // subroutineName
-        let subroutine_name = {
-            tokenizer.advance()?;
-            String::from(tokenizer.identifier())
-        };
+        compat: Option<Compat>,
+    ) -> Result<Vec<VmCommand>> {
+        let mut commands = Vec::new();
 
-        // ’(’
-        tokenizer.advance()?;
         // parameterList
-        ParameterListCompiler::compile(tokenizer, symbol_tables)?;
-        // ’)’
-        tokenizer.advance()?;
+        ParameterListCompiler::compile(&subroutine_dec.parameters, symbol_tables)?;
 
         // subroutineBody
         SubroutineBodyCompiler::compile(
-            tokenizer,
+            &subroutine_dec.body,
             symbol_tables,
-            &subroutine_name,
-            &subroutine_type,
-            written,
+            &subroutine_dec.name.name,
+            &subroutine_dec.kind,
+            &mut commands,
+            compat,
         )?;
 
-        Ok(())
+        Ok(commands)
     }
 }
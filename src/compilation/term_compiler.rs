@@ -1,141 +1,155 @@
-use std::io::Write;
-
 use anyhow::Result;
 
+use crate::ast::expression::{Expression, KeywordConstant, Term, UnaryOp, MAX_INTEGER_CONSTANT};
+use crate::ast::identifier::Identifier;
+use crate::compilation::compat::Compat;
 use crate::compilation::expression_compiler::ExpressionCompiler;
 use crate::compilation::subroutine_call_compiler::SubroutineCallCompiler;
 use crate::symbol_table::symbol_tables::SymbolTables;
-use crate::tokenizer::jack_tokenizer::JackTokenizer;
-use crate::tokenizer::key_word::KeyWord;
-use crate::tokenizer::token_type::TokenType;
 use crate::writer::command::Command;
+use crate::writer::label::LabelCreator;
 use crate::writer::segment::Segment;
-use crate::writer::vm_writer::VmWriter;
+use crate::writer::vm_command::VmCommand;
 
 /// term = integerConstant | stringConstant | keywordConstant | varName | varName ’[’ expression ’]’ | subroutineCall | ’(’ expression ’)’ | unaryOp term
 pub struct TermCompiler {}
 
 impl TermCompiler {
     pub fn compile(
-        tokenizer: &mut JackTokenizer,
+        term: &Term,
         symbol_tables: &mut SymbolTables,
-        written: &mut impl Write,
+        commands: &mut Vec<VmCommand>,
+        label_creator: &mut dyn LabelCreator,
+        compat: Option<Compat>,
     ) -> Result<()> {
-        match tokenizer.peek()?.token_type() {
-            TokenType::Keyword => {
-                if tokenizer.peek()?.is_keyword_constant()? {
-                    tokenizer.advance()?;
-                    match tokenizer.key_word()? {
-                        KeyWord::True => {
-                            VmWriter::write_push(&Segment::Constant, 1, written)?;
-                            VmWriter::write_arithmetic(&Command::Neg, written)?;
-                        }
-                        KeyWord::False | KeyWord::Null => {
-                            VmWriter::write_push(&Segment::Constant, 0, written)?
-                        }
-                        KeyWord::This => {
-                            // Set the base of the object to the base of this segment
-                            VmWriter::write_push(&Segment::Pointer, 0, written)?
-                        }
-                        _ => {}
-                    }
+        match term {
+            Term::KeywordConstant(keyword_constant, _) => match keyword_constant {
+                KeywordConstant::True if compat == Some(Compat::Reference) => {
+                    commands.push(VmCommand::Push(Segment::Constant, 0));
+                    commands.push(VmCommand::Arithmetic(Command::Not));
                 }
-            }
-            TokenType::Symbol => match tokenizer.peek()?.value().as_str() {
-                "(" => {
-                    // '('
-                    tokenizer.advance()?;
-                    // expression
-                    ExpressionCompiler::compile(tokenizer, symbol_tables, written)?;
-                    // ')'
-                    tokenizer.advance()?;
+                KeywordConstant::True => {
+                    commands.push(VmCommand::Push(Segment::Constant, 1));
+                    commands.push(VmCommand::Arithmetic(Command::Neg));
                 }
-                "-" => {
-                    // unaryOp
-                    tokenizer.advance()?;
-                    // term
-                    TermCompiler::compile(tokenizer, symbol_tables, written)?;
-                    VmWriter::write_arithmetic(&Command::Neg, written)?;
+                KeywordConstant::False | KeywordConstant::Null => {
+                    commands.push(VmCommand::Push(Segment::Constant, 0))
                 }
-                "~" => {
-                    // unaryOp
-                    tokenizer.advance()?;
-                    // term
-                    TermCompiler::compile(tokenizer, symbol_tables, written)?;
-                    VmWriter::write_arithmetic(&Command::Not, written)?;
+                KeywordConstant::This => {
+                    // Set the base of the object to the base of this segment
+                    commands.push(VmCommand::Push(Segment::Pointer, 0))
                 }
-                _ => {}
             },
-            TokenType::Identifier => {
-                match tokenizer.peek_second()?.value().as_str() {
-                    "[" => {
-                        // varName
-                        tokenizer.advance()?;
-                        let var_name = String::from(tokenizer.identifier());
-
-                        if let Some(symbol) = symbol_tables.get(&var_name) {
-                            VmWriter::write_push(
-                                &Segment::from(&symbol.kind),
-                                symbol.index,
-                                written,
-                            )?;
-                        }
-
-                        // '['
-                        tokenizer.advance()?;
-                        // expression
-                        ExpressionCompiler::compile(tokenizer, symbol_tables, written)?;
-                        // ']'
-                        tokenizer.advance()?;
-
-                        // add base address and index
-                        VmWriter::write_arithmetic(&Command::Add, written)?;
-
-                        // Use that segment to access var_name[expression]
-                        VmWriter::write_pop(&Segment::Pointer, 1, written)?;
-                        VmWriter::write_push(&Segment::That, 0, written)?;
-                    }
-                    "." | "(" => {
-                        SubroutineCallCompiler::compile(tokenizer, symbol_tables, written)?
-                    }
-                    _ => {
-                        // varName
-                        tokenizer.advance()?;
-                        let var_name = String::from(tokenizer.identifier());
-
-                        if let Some(symbol) = symbol_tables.get(&var_name) {
-                            VmWriter::write_push(
-                                &Segment::from(&symbol.kind),
-                                symbol.index,
-                                written,
-                            )?;
-                        }
-                    }
+            Term::Parenthesized(expression, _) => {
+                // '(' expression ')'
+                ExpressionCompiler::compile(
+                    expression,
+                    symbol_tables,
+                    commands,
+                    label_creator,
+                    compat,
+                )?;
+            }
+            _ if term.is_min_integer() => {
+                // -32768 can't be pushed as a constant, but it is ~32767
+                commands.push(VmCommand::Push(Segment::Constant, MAX_INTEGER_CONSTANT));
+                commands.push(VmCommand::Arithmetic(Command::Not));
+            }
+            Term::Unary(op, term, _) => {
+                // term
+                TermCompiler::compile(term, symbol_tables, commands, label_creator, compat)?;
+                // unaryOp
+                match op {
+                    UnaryOp::Neg => commands.push(VmCommand::Arithmetic(Command::Neg)),
+                    UnaryOp::Not => commands.push(VmCommand::Arithmetic(Command::Not)),
                 }
             }
-            TokenType::IntConst => {
-                tokenizer.advance()?;
-                VmWriter::write_push(&Segment::Constant, tokenizer.int_val()?, written)?;
+            Term::ArrayElement(var_name, index, _) => {
+                // varName '[' expression ']'
+                Self::compile_element_address(
+                    var_name,
+                    index,
+                    symbol_tables,
+                    commands,
+                    label_creator,
+                    compat,
+                )?;
+
+                // Use that segment to access var_name[expression]
+                commands.push(VmCommand::Pop(Segment::Pointer, 1));
+                commands.push(VmCommand::Push(Segment::That, 0));
+            }
+            Term::SubroutineCall(subroutine_call) => SubroutineCallCompiler::compile(
+                subroutine_call,
+                symbol_tables,
+                commands,
+                label_creator,
+                compat,
+            )?,
+            Term::VarName(var_name) => {
+                let symbol = symbol_tables.resolve(&var_name.name)?;
+                commands.push(VmCommand::Push(Segment::from(&symbol.kind), symbol.index));
+            }
+            Term::IntegerConstant(value, _) | Term::CharConstant(value, _) => {
+                commands.push(VmCommand::Push(Segment::Constant, *value));
             }
-            TokenType::StringConst => {
-                tokenizer.advance()?;
-                let value = tokenizer.string_val();
-                VmWriter::write_push(&Segment::Constant, value.len(), written)?;
-                VmWriter::write_call("String.new", 1, written)?;
+            Term::BitPattern(value, _) => match *value {
+                0..=MAX_INTEGER_CONSTANT => {
+                    commands.push(VmCommand::Push(Segment::Constant, *value));
+                }
+                // 0x8000 is -32768, which is ~32767
+                0x8000 => {
+                    commands.push(VmCommand::Push(Segment::Constant, MAX_INTEGER_CONSTANT));
+                    commands.push(VmCommand::Arithmetic(Command::Not));
+                }
+                // the upper half of the 16-bit patterns are the negative numbers
+                _ => {
+                    commands.push(VmCommand::Push(
+                        Segment::Constant,
+                        u16::MAX as usize + 1 - value,
+                    ));
+                    commands.push(VmCommand::Arithmetic(Command::Neg));
+                }
+            },
+            Term::StringConstant(value, _) => {
+                commands.push(VmCommand::Push(Segment::Constant, value.chars().count()));
+                commands.push(VmCommand::Call(String::from("String.new"), 1));
                 for c in value.chars() {
                     let unicode_hex = format!("{:x}", c as u32);
-                    VmWriter::write_push(
-                        &Segment::Constant,
+                    commands.push(VmCommand::Push(
+                        Segment::Constant,
                         usize::from_str_radix(&unicode_hex, 16)?,
-                        written,
-                    )?;
-                    VmWriter::write_call("String.appendChar", 2, written)?;
+                    ));
+                    commands.push(VmCommand::Call(String::from("String.appendChar"), 2));
                 }
             }
         }
 
         Ok(())
     }
+
+    /// Pushes the address of `varName[expression]`, the base address of the array plus the index.
+    pub fn compile_element_address(
+        var_name: &Identifier,
+        index: &Expression,
+        symbol_tables: &mut SymbolTables,
+        commands: &mut Vec<VmCommand>,
+        label_creator: &mut dyn LabelCreator,
+        compat: Option<Compat>,
+    ) -> Result<()> {
+        if compat == Some(Compat::Reference) {
+            // the reference compiler pushes the index first
+            ExpressionCompiler::compile(index, symbol_tables, commands, label_creator, compat)?;
+            let symbol = symbol_tables.resolve(&var_name.name)?;
+            commands.push(VmCommand::Push(Segment::from(&symbol.kind), symbol.index));
+        } else {
+            let symbol = symbol_tables.resolve(&var_name.name)?;
+            commands.push(VmCommand::Push(Segment::from(&symbol.kind), symbol.index));
+            ExpressionCompiler::compile(index, symbol_tables, commands, label_creator, compat)?;
+        }
+        commands.push(VmCommand::Arithmetic(Command::Add));
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -143,9 +157,16 @@ mod tests {
     use std::io::{Seek, Write};
 
     use crate::compilation::term_compiler::TermCompiler;
+    use crate::parsing::precedence::Precedence;
+    use crate::parsing::term_parser::TermParser;
     use crate::symbol_table::kind::Kind;
     use crate::symbol_table::symbol_tables::SymbolTables;
+    use crate::tokenizer::dialect::Dialect;
     use crate::tokenizer::jack_tokenizer::JackTokenizer;
+    use crate::tokenizer::span::Span;
+    use crate::writer::label::CounterLabelCreator;
+    use crate::writer::vm_command::VmCommand;
+    use crate::writer::vm_writer::VmWriter;
 
     #[test]
     fn can_compile_identifier() {
@@ -153,14 +174,23 @@ mod tests {
         writeln!(src_file, "value & mask").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
+        let mut commands = Vec::<VmCommand>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let term = TermParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("this", "Test", &Kind::Argument);
-        symbol_tables.define("value", "int", &Kind::Argument);
-
-        let result = TermCompiler::compile(&mut tokenizer, &mut symbol_tables, &mut output);
+        symbol_tables.define("this", "Test", &Kind::Argument, &Span::default());
+        symbol_tables.define("value", "int", &Kind::Argument, &Span::default());
+
+        let result = TermCompiler::compile(
+            &term,
+            &mut symbol_tables,
+            &mut commands,
+            &mut CounterLabelCreator::default(),
+            None,
+        );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
 
         assert!(result.is_ok());
@@ -173,18 +203,88 @@ mod tests {
         writeln!(src_file, "1").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
+        let mut commands = Vec::<VmCommand>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let term = TermParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
 
-        let result = TermCompiler::compile(&mut tokenizer, &mut symbol_tables, &mut output);
+        let result = TermCompiler::compile(
+            &term,
+            &mut symbol_tables,
+            &mut commands,
+            &mut CounterLabelCreator::default(),
+            None,
+        );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
 
         assert!(result.is_ok());
         assert_eq!("push constant 1\n", actual);
     }
 
+    #[test]
+    fn can_compile_bit_patterns() {
+        for (source, expected) in [
+            ("0x4000", "push constant 16384\n"),
+            ("0b1010", "push constant 10\n"),
+            ("0xFFFF", "push constant 1\nneg\n"),
+            ("0x8000", "push constant 32767\nnot\n"),
+        ] {
+            let mut src_file = tempfile::NamedTempFile::new().unwrap();
+            writeln!(src_file, "{source}").unwrap();
+            src_file.rewind().unwrap();
+            let path = src_file.path();
+            let mut commands = Vec::<VmCommand>::new();
+
+            let mut tokenizer = JackTokenizer::with_dialect(path, Dialect::Extended).unwrap();
+            let term = TermParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+            let mut symbol_tables = SymbolTables::new();
+
+            let result = TermCompiler::compile(
+                &term,
+                &mut symbol_tables,
+                &mut commands,
+                &mut CounterLabelCreator::default(),
+                None,
+            );
+            let mut output = Vec::<u8>::new();
+            VmWriter::write(&commands, &mut output).unwrap();
+            let actual = String::from_utf8(output).unwrap();
+
+            assert!(result.is_ok());
+            assert_eq!(expected, actual, "{source}");
+        }
+    }
+
+    #[test]
+    fn can_compile_min_int_const() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "-32768").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+        let mut commands = Vec::<VmCommand>::new();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let term = TermParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+        let mut symbol_tables = SymbolTables::new();
+
+        let result = TermCompiler::compile(
+            &term,
+            &mut symbol_tables,
+            &mut commands,
+            &mut CounterLabelCreator::default(),
+            None,
+        );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
+        let actual = String::from_utf8(output).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!("push constant 32767\nnot\n", actual);
+    }
+
     #[test]
     fn can_compile_string_const() {
         let expected = "\
@@ -205,12 +305,21 @@ call String.appendChar 2
         writeln!(src_file, "\"How? \"").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
+        let mut commands = Vec::<VmCommand>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let term = TermParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
 
-        let result = TermCompiler::compile(&mut tokenizer, &mut symbol_tables, &mut output);
+        let result = TermCompiler::compile(
+            &term,
+            &mut symbol_tables,
+            &mut commands,
+            &mut CounterLabelCreator::default(),
+            None,
+        );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
 
         assert!(result.is_ok());
@@ -227,12 +336,21 @@ neg
         writeln!(src_file, "-1").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
+        let mut commands = Vec::<VmCommand>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let term = TermParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
 
-        let result = TermCompiler::compile(&mut tokenizer, &mut symbol_tables, &mut output);
+        let result = TermCompiler::compile(
+            &term,
+            &mut symbol_tables,
+            &mut commands,
+            &mut CounterLabelCreator::default(),
+            None,
+        );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
 
         assert!(result.is_ok());
@@ -252,14 +370,23 @@ push that 0
         writeln!(src_file, "a[i]").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
+        let mut commands = Vec::<VmCommand>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let term = TermParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("a", "Array", &Kind::Var);
-        symbol_tables.define("i", "int", &Kind::Var);
-
-        let result = TermCompiler::compile(&mut tokenizer, &mut symbol_tables, &mut output);
+        symbol_tables.define("a", "Array", &Kind::Var, &Span::default());
+        symbol_tables.define("i", "int", &Kind::Var, &Span::default());
+
+        let result = TermCompiler::compile(
+            &term,
+            &mut symbol_tables,
+            &mut commands,
+            &mut CounterLabelCreator::default(),
+            None,
+        );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
 
         assert!(result.is_ok());
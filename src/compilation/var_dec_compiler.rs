@@ -1,45 +1,22 @@
 use anyhow::Result;
 
+use crate::ast::class::VarDec;
 use crate::symbol_table::kind::Kind;
 use crate::symbol_table::symbol_tables::SymbolTables;
-use crate::tokenizer::jack_tokenizer::JackTokenizer;
-use crate::tokenizer::token_type::TokenType::Symbol;
 
 /// varDec = ’var’ type varName (’,’ varName)* ’;’
 pub struct VarDecCompiler {}
 
 impl VarDecCompiler {
-    pub fn compile(tokenizer: &mut JackTokenizer, symbol_tables: &mut SymbolTables) -> Result<()> {
-        // ’var’
-        tokenizer.advance()?;
-
+    pub fn compile(var_dec: &VarDec, symbol_tables: &mut SymbolTables) -> Result<()> {
         // type
-        let type_name = {
-            tokenizer.advance()?;
-            String::from(tokenizer.identifier())
-        };
-
-        // varName
-        let var_name = {
-            tokenizer.advance()?;
-            String::from(tokenizer.identifier())
-        };
-        symbol_tables.define(&var_name, &type_name, &Kind::Var);
+        let type_name = var_dec.type_name.to_string();
 
-        // (’,’ varName)*
-        while tokenizer.peek()?.token_type() == &Symbol && tokenizer.peek()?.value() == "," {
-            // ','
-            tokenizer.advance()?;
-
-            // varName
-            tokenizer.advance()?;
-            let var_name = String::from(tokenizer.identifier());
-            symbol_tables.define(&var_name, &type_name, &Kind::Var);
+        // varName (’,’ varName)*
+        for var_name in &var_dec.var_names {
+            symbol_tables.define(&var_name.name, &type_name, &Kind::Var, &var_name.span);
         }
 
-        // ’;’
-        tokenizer.advance()?;
-
         Ok(())
     }
 }
@@ -49,6 +26,7 @@ mod tests {
     use std::io::{Seek, Write};
 
     use crate::compilation::var_dec_compiler::VarDecCompiler;
+    use crate::parsing::var_dec_parser::VarDecParser;
     use crate::symbol_table::kind::Kind;
     use crate::symbol_table::symbol_tables::SymbolTables;
     use crate::tokenizer::jack_tokenizer::JackTokenizer;
@@ -61,9 +39,10 @@ mod tests {
         let path = src_file.path();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let var_dec = VarDecParser::parse(&mut tokenizer).unwrap();
         let mut symbol_tables = SymbolTables::new();
 
-        let result = VarDecCompiler::compile(&mut tokenizer, &mut symbol_tables);
+        let result = VarDecCompiler::compile(&var_dec, &mut symbol_tables);
 
         assert!(result.is_ok());
         assert_eq!(3, symbol_tables.var_count(Kind::Var));
@@ -1,53 +1,53 @@
-use std::io::Write;
-
 use anyhow::Result;
 
+use crate::ast::statement::WhileStatement;
+use crate::compilation::compat::Compat;
 use crate::compilation::expression_compiler::ExpressionCompiler;
 use crate::compilation::statements_compiler::StatementsCompiler;
 use crate::symbol_table::symbol_tables::SymbolTables;
-use crate::tokenizer::jack_tokenizer::JackTokenizer;
 use crate::writer::command::Command;
 use crate::writer::label::LabelCreator;
-use crate::writer::vm_writer::VmWriter;
+use crate::writer::vm_command::VmCommand;
 
 /// whileStatement = ’while’ ’(’ expression ’)’ ’{’ statements ’}’
 pub struct WhileStatementCompiler {}
 
 impl WhileStatementCompiler {
     pub fn compile(
-        tokenizer: &mut JackTokenizer,
+        while_statement: &WhileStatement,
         symbol_tables: &mut SymbolTables,
-        written: &mut impl Write,
-        label_creator: &dyn LabelCreator,
+        commands: &mut Vec<VmCommand>,
+        label_creator: &mut dyn LabelCreator,
+        compat: Option<Compat>,
     ) -> Result<()> {
-        // while
-        tokenizer.advance()?;
-        // ’(’
-        tokenizer.advance()?;
-
-        let label_goto = label_creator.create("goto");
-        VmWriter::write_label(&label_goto, written)?;
+        let index = label_creator.next_index("WHILE");
+        let label_exp = format!("WHILE_EXP{index}");
+        let label_end = format!("WHILE_END{index}");
+        commands.push(VmCommand::Label(label_exp.clone()));
 
         // expression
-        ExpressionCompiler::compile(tokenizer, symbol_tables, written)?;
-        VmWriter::write_arithmetic(&Command::Not, written)?;
-
-        let label_if: String = label_creator.create("if");
-        VmWriter::write_if(&label_if, written)?;
-
-        // ’)’
-        tokenizer.advance()?;
-        // ’{’
-        tokenizer.advance()?;
+        ExpressionCompiler::compile(
+            &while_statement.condition,
+            symbol_tables,
+            commands,
+            label_creator,
+            compat,
+        )?;
+        commands.push(VmCommand::Arithmetic(Command::Not));
+        commands.push(VmCommand::IfGoto(label_end.clone()));
 
         // statements
-        StatementsCompiler::compile(tokenizer, symbol_tables, written)?;
+        StatementsCompiler::compile(
+            &while_statement.statements,
+            symbol_tables,
+            commands,
+            label_creator,
+            compat,
+        )?;
 
-        VmWriter::write_goto(&label_goto, written)?;
-        VmWriter::write_label(&label_if, written)?;
+        commands.push(VmCommand::Goto(label_exp));
+        commands.push(VmCommand::Label(label_end));
 
-        // ’}’
-        tokenizer.advance()?;
         Ok(())
     }
 }
@@ -59,26 +59,31 @@ mod tests {
     use mockall::predicate::eq;
 
     use crate::compilation::while_statement_compiler::WhileStatementCompiler;
+    use crate::parsing::precedence::Precedence;
+    use crate::parsing::while_statement_parser::WhileStatementParser;
     use crate::symbol_table::kind::Kind;
     use crate::symbol_table::symbol_tables::SymbolTables;
     use crate::tokenizer::jack_tokenizer::JackTokenizer;
+    use crate::tokenizer::span::Span;
     use crate::writer::label::MockLabelCreator;
+    use crate::writer::vm_command::VmCommand;
+    use crate::writer::vm_writer::VmWriter;
 
     #[test]
     fn can_compile() {
         let expected = "\
-label goto_L2
+label WHILE_EXP0
 push local 0
 not
-if-goto if_L1
+if-goto WHILE_END0
 push constant 8000
 push local 1
 add
 push constant 1
 call Memory.poke 2
 pop temp 0
-goto goto_L2
-label if_L1
+goto WHILE_EXP0
+label WHILE_END0
 ";
 
         let mut src_file = tempfile::NamedTempFile::new().unwrap();
@@ -87,29 +92,30 @@ label if_L1
         writeln!(src_file, "}}").unwrap();
         src_file.rewind().unwrap();
         let path = src_file.path();
-        let mut output = Vec::<u8>::new();
+        let mut commands = Vec::<VmCommand>::new();
 
         let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let while_statement =
+            WhileStatementParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("loop", "boolean", &Kind::Var);
-        symbol_tables.define("position", "int", &Kind::Var);
+        symbol_tables.define("loop", "boolean", &Kind::Var, &Span::default());
+        symbol_tables.define("position", "int", &Kind::Var, &Span::default());
 
         let mut mock_label_creator = MockLabelCreator::default();
         mock_label_creator
-            .expect_create()
-            .with(eq("if"))
-            .return_const(String::from("if_L1"));
-        mock_label_creator
-            .expect_create()
-            .with(eq("goto"))
-            .return_const(String::from("goto_L2"));
+            .expect_next_index()
+            .with(eq("WHILE"))
+            .return_const(0usize);
 
         let result = WhileStatementCompiler::compile(
-            &mut tokenizer,
+            &while_statement,
             &mut symbol_tables,
-            &mut output,
-            &mock_label_creator,
+            &mut commands,
+            &mut mock_label_creator,
+            None,
         );
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
 
         assert!(result.is_ok());
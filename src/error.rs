@@ -0,0 +1,3 @@
+pub mod diagnostic;
+pub mod diagnostics;
+pub mod severity;
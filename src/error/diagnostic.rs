@@ -0,0 +1,133 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::error::severity::Severity;
+use crate::tokenizer::span::Span;
+
+/// A problem found in a source file, reported with its location.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub message: String,
+    pub file_name: String,
+    pub span: Span,
+    pub help: Option<String>,
+    /// Related locations, with the file each of them is in
+    pub notes: Vec<(String, String, Span)>,
+    pub severity: Severity,
+}
+
+impl Diagnostic {
+    pub fn new(message: &str, file_name: &str, span: &Span) -> Self {
+        Diagnostic {
+            message: String::from(message),
+            file_name: String::from(file_name),
+            span: *span,
+            help: None,
+            notes: vec![],
+            severity: Severity::Error,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn warning(message: &str, file_name: &str, span: &Span) -> Self {
+        Diagnostic {
+            severity: Severity::Warning,
+            ..Self::new(message, file_name, span)
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    /// Adds a hint on how to fix the problem.
+    pub fn with_help(self, help: &str) -> Self {
+        Diagnostic {
+            help: Some(String::from(help)),
+            ..self
+        }
+    }
+
+    /// Adds a related location of the same file, such as an earlier declaration.
+    pub fn with_note(self, note: &str, span: &Span) -> Self {
+        let file_name = self.file_name.clone();
+        self.with_note_in(note, &file_name, span)
+    }
+
+    /// Adds a related location of another file.
+    pub fn with_note_in(mut self, note: &str, file_name: &str, span: &Span) -> Self {
+        self.notes
+            .push((String::from(note), String::from(file_name), *span));
+        self
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at {}:{}:{}",
+            self.message, self.file_name, self.span.line, self.span.column
+        )?;
+        for (note, file_name, span) in &self.notes {
+            write!(
+                f,
+                "\n  = note: {note} at {file_name}:{}:{}",
+                span.line, span.column
+            )?;
+        }
+        if let Some(help) = &self.help {
+            write!(f, "\n  = help: {help}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for Diagnostic {}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::diagnostic::Diagnostic;
+    use crate::tokenizer::span::Span;
+
+    #[test]
+    fn can_display_with_location() {
+        let diagnostic = Diagnostic::new(
+            "expected `;` after let statement, found `}`",
+            "Main.jack",
+            &Span::new(0, 120, 12, 5, 1),
+        );
+        assert_eq!(
+            "expected `;` after let statement, found `}` at Main.jack:12:5",
+            diagnostic.to_string()
+        );
+    }
+
+    #[test]
+    fn can_display_with_help() {
+        let diagnostic = Diagnostic::new(
+            "undefined variable `countr`",
+            "Main.jack",
+            &Span::new(0, 80, 5, 13, 6),
+        )
+        .with_help("did you mean `counter`?");
+        assert_eq!(
+            "undefined variable `countr` at Main.jack:5:13\n  = help: did you mean `counter`?",
+            diagnostic.to_string()
+        );
+    }
+
+    #[test]
+    fn can_display_with_note() {
+        let diagnostic = Diagnostic::new(
+            "duplicate declaration of `x`",
+            "Main.jack",
+            &Span::new(0, 60, 4, 17, 1),
+        )
+        .with_note("`x` is first declared here", &Span::new(0, 40, 3, 17, 1));
+        assert_eq!(
+            "duplicate declaration of `x` at Main.jack:4:17\n  = note: `x` is first declared here at Main.jack:3:17",
+            diagnostic.to_string()
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::slice::Iter;
+
+use crate::error::diagnostic::Diagnostic;
+
+pub const DEFAULT_ERROR_LIMIT: usize = 20;
+
+/// Diagnostics collected while compiling a file, up to a limit.
+#[derive(Debug)]
+pub struct Diagnostics {
+    diagnostics: Vec<Diagnostic>,
+    limit: usize,
+}
+
+impl Diagnostics {
+    pub fn new(limit: usize) -> Self {
+        Diagnostics {
+            diagnostics: vec![],
+            limit,
+        }
+    }
+
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.diagnostics.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.diagnostics.iter().filter(|d| d.is_error()).count()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(|d| d.is_error())
+    }
+
+    /// Returns true if no more diagnostics should be collected.
+    /// Only errors count towards the limit.
+    pub fn is_full(&self) -> bool {
+        self.error_count() >= self.limit
+    }
+
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+    }
+
+    /// Moves the collected diagnostics out, keeping the limit.
+    pub fn take(&mut self) -> Diagnostics {
+        Diagnostics {
+            diagnostics: std::mem::take(&mut self.diagnostics),
+            limit: self.limit,
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, Diagnostic> {
+        self.diagnostics.iter()
+    }
+}
+
+impl Display for Diagnostics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self.diagnostics.iter().map(|d| d.to_string()).collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl Error for Diagnostics {}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::diagnostic::Diagnostic;
+    use crate::error::diagnostics::Diagnostics;
+    use crate::tokenizer::span::Span;
+
+    #[test]
+    fn can_be_full_at_limit() {
+        let mut diagnostics = Diagnostics::new(2);
+        diagnostics.push(Diagnostic::new("first", "Main.jack", &Span::default()));
+        assert!(!diagnostics.is_full());
+        diagnostics.push(Diagnostic::new("second", "Main.jack", &Span::default()));
+        assert!(diagnostics.is_full());
+        assert_eq!(2, diagnostics.len());
+    }
+
+    #[test]
+    fn can_ignore_warnings_for_limit() {
+        let mut diagnostics = Diagnostics::new(1);
+        diagnostics.push(Diagnostic::warning("first", "Main.jack", &Span::default()));
+        assert!(!diagnostics.is_full());
+        assert!(!diagnostics.has_errors());
+        diagnostics.push(Diagnostic::new("second", "Main.jack", &Span::default()));
+        assert!(diagnostics.is_full());
+        assert_eq!(1, diagnostics.error_count());
+    }
+}
@@ -1,14 +1,28 @@
-use std::fs::File;
+use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use walkdir::{DirEntry, WalkDir};
 
+use crate::analysis::lint::{Lint, LintLevels};
+use crate::compilation::compat::Compat;
 use crate::compilation::compilation_engine::CompilationEngine;
+use crate::compilation::program::Program;
+use crate::error::diagnostics::{Diagnostics, DEFAULT_ERROR_LIMIT};
+use crate::parsing::precedence::Precedence;
+use crate::tokenizer::dialect::Dialect;
 use crate::tokenizer::jack_tokenizer::JackTokenizer;
+use crate::writer::parse_tree_writer::ParseTreeWriter;
+use crate::writer::xml_writer::XmlWriter;
 
+mod analysis;
+mod ast;
 mod compilation;
+mod error;
+mod optimization;
+mod parsing;
 mod symbol_table;
 mod tokenizer;
 mod writer;
@@ -20,9 +34,61 @@ struct Args {
     /// Sets a source to be compiled. The source is a jack file or directory.
     #[arg(value_name = "SOURCE")]
     path: PathBuf,
+
+    /// Sets the number of errors reported for a file before its compilation stops.
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_ERROR_LIMIT)]
+    max_errors: usize,
+
+    /// Sets the flavour of Jack accepted in sources.
+    #[arg(long, value_enum, default_value_t = Dialect::Standard)]
+    dialect: Dialect,
+
+    /// Sets how operators without parentheses are grouped.
+    #[arg(long, value_enum, default_value_t = Precedence::Jack)]
+    precedence: Precedence,
+
+    /// Sets the kind of output written next to each jack file.
+    #[arg(long, value_enum, default_value_t = Emit::Vm)]
+    emit: Emit,
+
+    /// Silences a kind of warning. Can be repeated.
+    #[arg(short = 'A', long = "allow", value_enum, value_name = "LINT")]
+    allowed: Vec<Lint>,
+
+    /// Reports warnings as errors.
+    #[arg(long)]
+    strict: bool,
+
+    /// Generates the same VM code as another compiler.
+    #[arg(long, value_enum, value_name = "COMPILER")]
+    compat: Option<Compat>,
+
+    /// Shrinks the generated VM code and prints how much it saved.
+    #[arg(short = 'O', long, conflicts_with = "compat")]
+    optimize: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Emit {
+    /// VM code (`Foo.vm`)
+    Vm,
+    /// Tokens as XML (`FooT.xml`)
+    TokensXml,
+    /// Parse tree as XML (`Foo.xml`)
+    Xml,
 }
 
-fn main() -> Result<()> {
+impl Emit {
+    fn file_suffix(&self) -> &str {
+        match self {
+            Emit::Vm => ".vm",
+            Emit::TokensXml => "T.xml",
+            Emit::Xml => ".xml",
+        }
+    }
+}
+
+fn main() -> Result<ExitCode> {
     let args = Args::parse();
     let files: Vec<DirEntry> = extract_files_from(args.path.as_path());
 
@@ -30,16 +96,106 @@ fn main() -> Result<()> {
         println!(
             "The compilation target doesn't exist. Set a jack file or directory with jack files."
         );
-        return Ok(());
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mut tokenizers = Vec::new();
+    for (file_id, file) in files.iter().enumerate() {
+        let mut tokenizer = JackTokenizer::with_file_id(file.path(), file_id, args.dialect)?;
+        tokenizer.set_error_limit(args.max_errors);
+        tokenizers.push(tokenizer);
     }
 
-    for file in files {
-        let mut output_file = File::create(create_output_file_name(file.path()))?;
-        let mut engine = CompilationEngine::new(JackTokenizer::new(file.path())?);
-        engine.compile(&mut output_file)?;
+    // The output file is only written when the whole class compiles.
+    let results: Vec<Result<(Vec<u8>, Diagnostics)>> = match args.emit {
+        Emit::Vm => {
+            let lint_levels = LintLevels::new(&args.allowed, args.strict);
+            let mut program = Program::new(
+                tokenizers,
+                &lint_levels,
+                args.compat,
+                args.optimize,
+                args.precedence,
+            );
+            program.set_error_limit(args.max_errors);
+            // a single file can call the classes of the files next to it
+            if args.path.is_file() {
+                let siblings = extract_siblings_of(&args.path)
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, file)| {
+                        JackTokenizer::with_file_id(file.path(), files.len() + index, args.dialect)
+                            .ok()
+                    })
+                    .collect();
+                program.set_siblings(siblings, args.precedence);
+            }
+            let results = program.compile();
+            if let Some(stats) = program.optimization_stats() {
+                println!("{stats}");
+            }
+            results
+        }
+        Emit::TokensXml => tokenizers
+            .into_iter()
+            .map(|mut tokenizer| {
+                let mut output = Vec::<u8>::new();
+                XmlWriter::write_tokens(&mut tokenizer, &mut output)
+                    .map(|()| (output, Diagnostics::new(args.max_errors)))
+            })
+            .collect(),
+        Emit::Xml => tokenizers
+            .into_iter()
+            .map(|tokenizer| {
+                let mut output = Vec::<u8>::new();
+                let mut engine = CompilationEngine::new(tokenizer);
+                engine.set_precedence(args.precedence);
+                engine
+                    .parse()
+                    .and_then(|class| ParseTreeWriter::write_class(&class, &mut output))
+                    .map(|()| (output, Diagnostics::new(args.max_errors)))
+            })
+            .collect(),
+    };
+
+    let mut exit_code = ExitCode::SUCCESS;
+    for (file, result) in files.iter().zip(results) {
+        match result {
+            Ok((output, warnings)) => {
+                print_diagnostics(&warnings);
+                fs::write(create_output_file_name(file.path(), args.emit), output)?
+            }
+            Err(error) => {
+                report(&error, file.path());
+                exit_code = ExitCode::FAILURE;
+            }
+        }
     }
 
-    Ok(())
+    Ok(exit_code)
+}
+
+fn report(error: &anyhow::Error, path: &Path) {
+    match error.downcast_ref::<Diagnostics>() {
+        Some(diagnostics) => {
+            print_diagnostics(diagnostics);
+            if diagnostics.is_full() {
+                eprintln!("note: stopped at the error limit, raise it with `--max-errors`");
+            }
+            eprintln!(
+                "error: could not compile `{}` due to {} previous error(s)",
+                path.display(),
+                diagnostics.error_count()
+            );
+        }
+        None => eprintln!("error: {error}"),
+    }
+}
+
+fn print_diagnostics(diagnostics: &Diagnostics) {
+    for diagnostic in diagnostics.iter() {
+        eprintln!("{}: {diagnostic}", diagnostic.severity);
+    }
 }
 
 fn extract_files_from(path: &Path) -> Vec<DirEntry> {
@@ -50,6 +206,21 @@ fn extract_files_from(path: &Path) -> Vec<DirEntry> {
         .collect()
 }
 
+/// Returns the other jack files in the directory of `path`.
+fn extract_siblings_of(path: &Path) -> Vec<DirEntry> {
+    let dir = match path.parent() {
+        Some(dir) if dir != Path::new("") => dir,
+        _ => Path::new("."),
+    };
+    WalkDir::new(dir)
+        .max_depth(1)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(is_jack_file)
+        .filter(|e| Some(e.file_name()) != path.file_name())
+        .collect()
+}
+
 fn is_jack_file(entry: &DirEntry) -> bool {
     entry
         .file_name()
@@ -58,12 +229,15 @@ fn is_jack_file(entry: &DirEntry) -> bool {
         .unwrap_or(false)
 }
 
-fn create_output_file_name(path: &Path) -> String {
+fn create_output_file_name(path: &Path, emit: Emit) -> String {
+    let suffix = emit.file_suffix();
     if path.is_file() && path.extension().unwrap() == "jack" {
-        return String::from(path.with_extension("vm").to_string_lossy());
+        let file_stem = path.file_stem().unwrap().to_string_lossy();
+        let file_name = format!("{file_stem}{suffix}");
+        return String::from(path.with_file_name(file_name).to_string_lossy());
     }
 
     let dir = path.to_string_lossy();
     let file_name = path.file_name().unwrap().to_string_lossy();
-    format!("{dir}/{file_name}.vm")
+    format!("{dir}/{file_name}{suffix}")
 }
@@ -0,0 +1,3 @@
+pub mod constant_folding;
+pub mod optimization_stats;
+pub mod peephole;
@@ -0,0 +1,273 @@
+use crate::ast::class::Class;
+use crate::ast::expression::{
+    BinaryOp, Expression, KeywordConstant, SubroutineCall, Term, UnaryOp, MAX_INTEGER_CONSTANT,
+};
+use crate::ast::statement::Statement;
+use crate::optimization::optimization_stats::OptimizationStats;
+use crate::tokenizer::span::Span;
+
+/// Evaluates operations on constants at compile time, with the 16-bit two's-complement arithmetic of the Hack platform.
+/// Operands are never reordered, so `x + 2 + 3` is `(x + 2) + 3` and stays as it is.
+pub struct ConstantFolding {}
+
+impl ConstantFolding {
+    pub fn fold_class(class: &mut Class, stats: &mut OptimizationStats) {
+        for subroutine_dec in &mut class.subroutine_decs {
+            Self::fold_statements(&mut subroutine_dec.body.statements, stats);
+        }
+    }
+
+    fn fold_statements(statements: &mut [Statement], stats: &mut OptimizationStats) {
+        for statement in statements {
+            match statement {
+                Statement::Let(let_statement) => {
+                    if let Some(index) = &mut let_statement.index {
+                        Self::fold_expression(index, stats);
+                    }
+                    Self::fold_expression(&mut let_statement.value, stats);
+                }
+                Statement::If(if_statement) => {
+                    Self::fold_expression(&mut if_statement.condition, stats);
+                    Self::fold_statements(&mut if_statement.statements, stats);
+                    if let Some(else_statements) = &mut if_statement.else_statements {
+                        Self::fold_statements(else_statements, stats);
+                    }
+                }
+                Statement::While(while_statement) => {
+                    Self::fold_expression(&mut while_statement.condition, stats);
+                    Self::fold_statements(&mut while_statement.statements, stats);
+                }
+                Statement::Do(do_statement) => {
+                    // the call is kept even when it is constant, since its value is discarded anyway
+                    Self::fold_arguments(&mut do_statement.subroutine_call, stats);
+                }
+                Statement::Return(return_statement) => {
+                    if let Some(value) = &mut return_statement.value {
+                        Self::fold_expression(value, stats);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Replaces the constant parts of the expression with their values.
+    /// Returns the value of the whole expression when it is a constant.
+    fn fold_expression(expression: &mut Expression, stats: &mut OptimizationStats) -> Option<i16> {
+        match expression {
+            Expression::Term(term) => Self::fold_term(term, stats),
+            Expression::Binary(binary) => {
+                // both sides are folded even when the first one is not a constant
+                let lhs = Self::fold_expression(&mut binary.lhs, stats);
+                let rhs = Self::fold_expression(&mut binary.rhs, stats);
+                let value = Self::apply(binary.op, lhs?, rhs?)?;
+                *expression = Expression::Term(Self::constant(value, binary.span));
+                stats.count("constant-folding");
+                Some(value)
+            }
+        }
+    }
+
+    fn fold_term(term: &mut Term, stats: &mut OptimizationStats) -> Option<i16> {
+        match term {
+            Term::IntegerConstant(value, _) | Term::CharConstant(value, _) => {
+                i16::try_from(*value).ok()
+            }
+            Term::BitPattern(value, _) => Some(*value as u16 as i16),
+            Term::KeywordConstant(KeywordConstant::True, _) => Some(-1),
+            Term::KeywordConstant(KeywordConstant::False, _) => Some(0),
+            Term::KeywordConstant(..) | Term::StringConstant(..) | Term::VarName(_) => None,
+            Term::ArrayElement(_, index, _) => {
+                Self::fold_expression(index, stats);
+                None
+            }
+            Term::Parenthesized(expression, _) => Self::fold_expression(expression, stats),
+            _ if term.is_min_integer() => Some(i16::MIN),
+            Term::Unary(op, operand, span) => {
+                // `-5` and `~false` are already as short as their value
+                let is_literal = matches!(
+                    **operand,
+                    Term::IntegerConstant(..)
+                        | Term::BitPattern(..)
+                        | Term::CharConstant(..)
+                        | Term::KeywordConstant(..)
+                );
+                let value = match op {
+                    UnaryOp::Neg => Self::fold_term(operand, stats)?.wrapping_neg(),
+                    UnaryOp::Not => !Self::fold_term(operand, stats)?,
+                };
+                if !is_literal {
+                    *term = Self::constant(value, *span);
+                    stats.count("constant-folding");
+                }
+                Some(value)
+            }
+            Term::SubroutineCall(subroutine_call) => {
+                let arguments = Self::fold_arguments(subroutine_call, stats);
+                let receiver = subroutine_call.receiver.as_ref().map(|r| r.name.as_str());
+                let subroutine_name = subroutine_call.subroutine_name.name.as_str();
+                let value = match (receiver, subroutine_name, arguments.as_slice()) {
+                    (Some("Math"), "multiply", [Some(x), Some(y)]) => x.wrapping_mul(*y),
+                    // dividing by zero is left to `Math.divide` to report at runtime
+                    (Some("Math"), "divide", [Some(x), Some(y)]) => x.checked_div(*y)?,
+                    _ => return None,
+                };
+                *term = Self::constant(value, subroutine_call.span);
+                stats.count("constant-folding");
+                Some(value)
+            }
+        }
+    }
+
+    fn fold_arguments(
+        subroutine_call: &mut SubroutineCall,
+        stats: &mut OptimizationStats,
+    ) -> Vec<Option<i16>> {
+        subroutine_call
+            .arguments
+            .iter_mut()
+            .map(|argument| Self::fold_expression(argument, stats))
+            .collect()
+    }
+
+    fn apply(op: BinaryOp, x: i16, y: i16) -> Option<i16> {
+        let value = match op {
+            BinaryOp::Add => x.wrapping_add(y),
+            BinaryOp::Sub => x.wrapping_sub(y),
+            BinaryOp::Mul => x.wrapping_mul(y),
+            BinaryOp::Div => x.checked_div(y)?,
+            BinaryOp::And => x & y,
+            BinaryOp::Or => x | y,
+            BinaryOp::Lt => Self::boolean(x < y),
+            BinaryOp::Gt => Self::boolean(x > y),
+            BinaryOp::Eq => Self::boolean(x == y),
+            BinaryOp::LogicalAnd => Self::boolean(x != 0 && y != 0),
+            BinaryOp::LogicalOr => Self::boolean(x != 0 || y != 0),
+        };
+        Some(value)
+    }
+
+    fn boolean(value: bool) -> i16 {
+        if value {
+            -1
+        } else {
+            0
+        }
+    }
+
+    /// Returns the shortest term for a value, as `push constant` only takes 0..32767.
+    fn constant(value: i16, span: Span) -> Term {
+        match value {
+            0.. => Term::IntegerConstant(value as usize, span),
+            // `~32767` is -32768, which has no positive counterpart to negate
+            i16::MIN => Term::Unary(
+                UnaryOp::Not,
+                Box::new(Term::IntegerConstant(MAX_INTEGER_CONSTANT, span)),
+                span,
+            ),
+            _ => Term::Unary(
+                UnaryOp::Neg,
+                Box::new(Term::IntegerConstant(-value as usize, span)),
+                span,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::compilation::expression_compiler::ExpressionCompiler;
+    use crate::optimization::constant_folding::ConstantFolding;
+    use crate::optimization::optimization_stats::OptimizationStats;
+    use crate::parsing::expression_parser::ExpressionParser;
+    use crate::parsing::precedence::Precedence;
+    use crate::symbol_table::kind::Kind;
+    use crate::symbol_table::symbol_tables::SymbolTables;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+    use crate::tokenizer::span::Span;
+    use crate::writer::label::CounterLabelCreator;
+    use crate::writer::vm_command::VmCommand;
+    use crate::writer::vm_writer::VmWriter;
+
+    fn fold(source: &str) -> (String, OptimizationStats) {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "{source};").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+        let mut commands = Vec::<VmCommand>::new();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let mut expression = ExpressionParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+        let mut symbol_tables = SymbolTables::new();
+        symbol_tables.define("x", "int", &Kind::Var, &Span::default());
+        let mut stats = OptimizationStats::default();
+
+        ConstantFolding::fold_expression(&mut expression, &mut stats);
+        ExpressionCompiler::compile(
+            &expression,
+            &mut symbol_tables,
+            &mut commands,
+            &mut CounterLabelCreator::default(),
+            None,
+        )
+        .unwrap();
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
+        (String::from_utf8(output).unwrap(), stats)
+    }
+
+    #[test]
+    fn can_fold_arithmetic() {
+        let (actual, stats) = fold("Math.multiply(2, 3) + (-7 / 2) - (1 * 4)");
+
+        assert_eq!("push constant 1\nneg\n", actual);
+        assert_eq!(Some(&5), stats.rewrites.get("constant-folding"));
+    }
+
+    #[test]
+    fn can_fold_with_wrapping_arithmetic() {
+        assert_eq!("push constant 32767\nnot\n", fold("32767 + 1").0);
+        assert_eq!("push constant 32767\nnot\n", fold("-(32767 + 1)").0);
+        assert_eq!("push constant 1\nneg\n", fold("Math.multiply(255, 257)").0);
+    }
+
+    #[test]
+    fn can_fold_comparisons_and_logic() {
+        assert_eq!("push constant 0\n", fold("(1 < 2) & ~(3 = 3)").0);
+        assert_eq!("push constant 1\nneg\n", fold("~(1 > 2) | false").0);
+        assert_eq!("push constant 5\n", fold("~(-6)").0);
+    }
+
+    #[test]
+    fn can_keep_evaluation_order() {
+        let expected = "\
+push local 0
+push constant 2
+add
+push constant 3
+add
+push constant 5
+push local 0
+add
+add
+";
+
+        assert_eq!(expected, fold("x + 2 + 3 + (2 + 3 + x)").0);
+    }
+
+    #[test]
+    fn can_keep_division_by_zero() {
+        let expected = "\
+push constant 1
+push constant 0
+call Math.divide 2
+push constant 1
+push constant 0
+call Math.divide 2
+add
+";
+
+        assert_eq!(expected, fold("(1 / 0) + Math.divide(1, 0)").0);
+    }
+}
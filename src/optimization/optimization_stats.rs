@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// How much the optimizer shrank the generated code, and by which rewrites.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OptimizationStats {
+    /// Number of VM commands generated without optimizing
+    pub before: usize,
+    /// Number of VM commands after optimizing
+    pub after: usize,
+    /// How often each rewrite rule was applied, by rule name
+    pub rewrites: BTreeMap<&'static str, usize>,
+}
+
+impl OptimizationStats {
+    pub fn count(&mut self, rule: &'static str) {
+        *self.rewrites.entry(rule).or_default() += 1;
+    }
+
+    pub fn add(&mut self, other: &OptimizationStats) {
+        self.before += other.before;
+        self.after += other.after;
+        for (rule, count) in &other.rewrites {
+            *self.rewrites.entry(rule).or_default() += count;
+        }
+    }
+}
+
+impl Display for OptimizationStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // strength reduction trades a few more commands for faster ones
+        let (difference, comparison, sign) = if self.after <= self.before {
+            (self.before - self.after, "fewer", '-')
+        } else {
+            (self.after - self.before, "more", '+')
+        };
+        let percent = if self.before == 0 {
+            0.0
+        } else {
+            difference as f64 * 100.0 / self.before as f64
+        };
+        write!(
+            f,
+            "optimized {} VM commands to {} ({difference} {comparison}, {sign}{percent:.1}%)",
+            self.before, self.after
+        )?;
+        for (rule, count) in &self.rewrites {
+            write!(f, "\n  {rule}: {count}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::optimization::optimization_stats::OptimizationStats;
+
+    #[test]
+    fn can_display() {
+        let mut stats = OptimizationStats {
+            before: 200,
+            after: 150,
+            ..Default::default()
+        };
+        stats.count("double-not");
+        stats.count("push-pop");
+        stats.count("double-not");
+
+        assert_eq!(
+            "optimized 200 VM commands to 150 (50 fewer, -25.0%)\n  double-not: 2\n  push-pop: 1",
+            stats.to_string()
+        );
+    }
+
+    #[test]
+    fn can_display_growth() {
+        let stats = OptimizationStats {
+            before: 40,
+            after: 50,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            "optimized 40 VM commands to 50 (10 more, +25.0%)",
+            stats.to_string()
+        );
+    }
+}
@@ -0,0 +1,448 @@
+use crate::optimization::optimization_stats::OptimizationStats;
+use crate::writer::command::Command;
+use crate::writer::segment::Segment;
+use crate::writer::vm_command::VmCommand;
+
+/// A rewrite of a few consecutive VM commands into fewer or faster commands that behave the same.
+pub struct Rule {
+    pub name: &'static str,
+    /// Number of commands the rule looks at
+    pub length: usize,
+    /// Returns the replacement of the commands, or None if the rule doesn't apply to them.
+    pub rewrite: fn(&[VmCommand]) -> Option<Vec<VmCommand>>,
+}
+
+/// The rewrites applied by the peephole optimizer.
+pub const RULES: [Rule; 12] = [
+    Rule {
+        name: "double-not",
+        length: 2,
+        rewrite: double_not,
+    },
+    Rule {
+        name: "double-neg",
+        length: 2,
+        rewrite: double_neg,
+    },
+    Rule {
+        name: "negated-constant",
+        length: 3,
+        rewrite: negated_constant,
+    },
+    Rule {
+        name: "constant-condition",
+        length: 2,
+        rewrite: constant_condition,
+    },
+    Rule {
+        name: "constant-condition",
+        length: 3,
+        rewrite: unary_constant_condition,
+    },
+    Rule {
+        name: "push-pop",
+        length: 2,
+        rewrite: push_pop,
+    },
+    Rule {
+        name: "jump-to-next",
+        length: 2,
+        rewrite: jump_to_next,
+    },
+    Rule {
+        name: "unreachable",
+        length: 2,
+        rewrite: unreachable,
+    },
+    Rule {
+        name: "strength-reduction",
+        length: 3,
+        rewrite: multiply_pushed_value,
+    },
+    Rule {
+        name: "strength-reduction",
+        length: 3,
+        rewrite: multiply_constant_by_pushed_value,
+    },
+    Rule {
+        name: "strength-reduction",
+        length: 2,
+        rewrite: multiply,
+    },
+    Rule {
+        name: "strength-reduction",
+        length: 2,
+        rewrite: divide_by_one,
+    },
+];
+
+const MAX_RULE_LENGTH: usize = 3;
+
+/// The largest multiplier that is not a power of two and still gets replaced by additions
+const MAX_ADD_CHAIN: usize = 8;
+
+/// Shrinks and speeds up the VM code of a subroutine by rewriting short sequences of commands until no rule applies.
+pub struct Peephole {}
+
+impl Peephole {
+    pub fn optimize(mut commands: Vec<VmCommand>, stats: &mut OptimizationStats) -> Vec<VmCommand> {
+        let mut i = 0;
+        while i < commands.len() {
+            let applied = RULES.iter().find_map(|rule| {
+                let window = commands.get(i..i + rule.length)?;
+                (rule.rewrite)(window).map(|replacement| (rule, replacement))
+            });
+            match applied {
+                Some((rule, replacement)) => {
+                    commands.splice(i..i + rule.length, replacement);
+                    stats.count(rule.name);
+                    // the replacement may complete a pattern that starts before it
+                    i = i.saturating_sub(MAX_RULE_LENGTH - 1);
+                }
+                None => i += 1,
+            }
+        }
+        commands
+    }
+}
+
+/// `not; not` → nothing
+fn double_not(window: &[VmCommand]) -> Option<Vec<VmCommand>> {
+    match window {
+        [VmCommand::Arithmetic(Command::Not), VmCommand::Arithmetic(Command::Not)] => Some(vec![]),
+        _ => None,
+    }
+}
+
+/// `neg; neg` → nothing
+fn double_neg(window: &[VmCommand]) -> Option<Vec<VmCommand>> {
+    match window {
+        [VmCommand::Arithmetic(Command::Neg), VmCommand::Arithmetic(Command::Neg)] => Some(vec![]),
+        _ => None,
+    }
+}
+
+/// `push constant c; neg; not` → `push constant c-1`, since `~(-c)` is `c - 1`.
+/// This turns the `push constant 1; neg; not` of `~true` into `push constant 0`.
+fn negated_constant(window: &[VmCommand]) -> Option<Vec<VmCommand>> {
+    match window {
+        [VmCommand::Push(Segment::Constant, c), VmCommand::Arithmetic(Command::Neg), VmCommand::Arithmetic(Command::Not)]
+            if *c > 0 =>
+        {
+            Some(vec![VmCommand::Push(Segment::Constant, c - 1)])
+        }
+        _ => None,
+    }
+}
+
+/// `push constant 0; if-goto L` → nothing, `push constant c; if-goto L` → `goto L`
+fn constant_condition(window: &[VmCommand]) -> Option<Vec<VmCommand>> {
+    match window {
+        [VmCommand::Push(Segment::Constant, c), VmCommand::IfGoto(label)] => {
+            Some(jump_if(*c != 0, label))
+        }
+        _ => None,
+    }
+}
+
+/// `push constant c; neg; if-goto L` and `push constant c; not; if-goto L`
+/// → `goto L` when the result is true, nothing otherwise
+fn unary_constant_condition(window: &[VmCommand]) -> Option<Vec<VmCommand>> {
+    match window {
+        [VmCommand::Push(Segment::Constant, c), VmCommand::Arithmetic(Command::Neg), VmCommand::IfGoto(label)] => {
+            Some(jump_if(*c != 0, label))
+        }
+        // `~c` is never 0 for a constant in 0..32767
+        [VmCommand::Push(Segment::Constant, _), VmCommand::Arithmetic(Command::Not), VmCommand::IfGoto(label)] => {
+            Some(jump_if(true, label))
+        }
+        _ => None,
+    }
+}
+
+fn jump_if(condition: bool, label: &str) -> Vec<VmCommand> {
+    if condition {
+        vec![VmCommand::Goto(String::from(label))]
+    } else {
+        vec![]
+    }
+}
+
+/// `push s i; pop s i` → nothing
+fn push_pop(window: &[VmCommand]) -> Option<Vec<VmCommand>> {
+    match window {
+        [VmCommand::Push(push_segment, push_index), VmCommand::Pop(pop_segment, pop_index)]
+            if push_segment == pop_segment && push_index == pop_index =>
+        {
+            Some(vec![])
+        }
+        _ => None,
+    }
+}
+
+/// `goto L; label L` → `label L`
+fn jump_to_next(window: &[VmCommand]) -> Option<Vec<VmCommand>> {
+    match window {
+        [VmCommand::Goto(target), VmCommand::Label(label)] if target == label => {
+            Some(vec![VmCommand::Label(String::from(label))])
+        }
+        _ => None,
+    }
+}
+
+/// `goto L; c` → `goto L` and `return; c` → `return` unless `c` is a label,
+/// because nothing can jump to `c`
+fn unreachable(window: &[VmCommand]) -> Option<Vec<VmCommand>> {
+    match window {
+        [jump @ (VmCommand::Goto(_) | VmCommand::Return), next]
+            if !matches!(next, VmCommand::Label(_) | VmCommand::Function(..)) =>
+        {
+            Some(vec![jump.clone()])
+        }
+        _ => None,
+    }
+}
+
+/// `push x; push constant c; call Math.multiply 2` → `push x; push x; add` and the like
+fn multiply_pushed_value(window: &[VmCommand]) -> Option<Vec<VmCommand>> {
+    match window {
+        [push @ VmCommand::Push(..), VmCommand::Push(Segment::Constant, c), VmCommand::Call(name, 2)]
+            if name == "Math.multiply" =>
+        {
+            multiply_by_constant(Some(push), *c)
+        }
+        _ => None,
+    }
+}
+
+/// `push constant c; push x; call Math.multiply 2` → `push x; push x; add` and the like
+fn multiply_constant_by_pushed_value(window: &[VmCommand]) -> Option<Vec<VmCommand>> {
+    match window {
+        [VmCommand::Push(Segment::Constant, c), push @ VmCommand::Push(..), VmCommand::Call(name, 2)]
+            if name == "Math.multiply" =>
+        {
+            multiply_by_constant(Some(push), *c)
+        }
+        _ => None,
+    }
+}
+
+/// `push constant c; call Math.multiply 2` → additions of the value on the stack, kept in `temp 0`
+fn multiply(window: &[VmCommand]) -> Option<Vec<VmCommand>> {
+    match window {
+        [VmCommand::Push(Segment::Constant, c), VmCommand::Call(name, 2)]
+            if name == "Math.multiply" =>
+        {
+            multiply_by_constant(None, *c)
+        }
+        _ => None,
+    }
+}
+
+/// `push constant 1; call Math.divide 2` → nothing
+fn divide_by_one(window: &[VmCommand]) -> Option<Vec<VmCommand>> {
+    match window {
+        [VmCommand::Push(Segment::Constant, 1), VmCommand::Call(name, 2)]
+            if name == "Math.divide" =>
+        {
+            Some(vec![])
+        }
+        _ => None,
+    }
+}
+
+/// Returns the additions that multiply a value by `c`, or None if `c` is too large for them to pay off.
+/// The value is the one pushed by `operand` when it is known, since pushing it again has no side effects.
+/// Otherwise it is the value on the stack, which is kept in `temp 0` to be pushed again.
+fn multiply_by_constant(operand: Option<&VmCommand>, c: usize) -> Option<Vec<VmCommand>> {
+    let add = VmCommand::Arithmetic(Command::Add);
+    let pop_temp = VmCommand::Pop(Segment::Temp, 0);
+    let push_temp = VmCommand::Push(Segment::Temp, 0);
+
+    let mut commands = Vec::new();
+    match (operand, c) {
+        (Some(_), 0) => commands.push(VmCommand::Push(Segment::Constant, 0)),
+        // the value on the stack is dropped, but the code computing it still runs
+        (None, 0) => commands.extend([pop_temp, VmCommand::Push(Segment::Constant, 0)]),
+        (_, 1) => commands.extend(operand.cloned()),
+        (_, _) if c.is_power_of_two() => {
+            let mut doublings = c.trailing_zeros();
+            if let Some(push) = operand {
+                commands.extend([push.clone(), push.clone(), add.clone()]);
+                doublings -= 1;
+            }
+            for _ in 0..doublings {
+                commands.extend([
+                    pop_temp.clone(),
+                    push_temp.clone(),
+                    push_temp.clone(),
+                    add.clone(),
+                ]);
+            }
+        }
+        (_, 2..=MAX_ADD_CHAIN) => {
+            let push = match operand {
+                Some(push) => push.clone(),
+                None => {
+                    commands.push(pop_temp);
+                    push_temp
+                }
+            };
+            commands.push(push.clone());
+            for _ in 1..c {
+                commands.extend([push.clone(), add.clone()]);
+            }
+        }
+        _ => return None,
+    }
+    Some(commands)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::optimization::optimization_stats::OptimizationStats;
+    use crate::optimization::peephole::Peephole;
+    use crate::writer::command::Command;
+    use crate::writer::segment::Segment;
+    use crate::writer::vm_command::VmCommand;
+    use crate::writer::vm_writer::VmWriter;
+
+    #[test]
+    fn can_remove_infinite_loop_condition() {
+        // while (true) { do Main.step(); }
+        let commands = vec![
+            VmCommand::Label(String::from("WHILE_EXP0")),
+            VmCommand::Push(Segment::Constant, 1),
+            VmCommand::Arithmetic(Command::Neg),
+            VmCommand::Arithmetic(Command::Not),
+            VmCommand::IfGoto(String::from("WHILE_END0")),
+            VmCommand::Call(String::from("Main.step"), 0),
+            VmCommand::Pop(Segment::Temp, 0),
+            VmCommand::Goto(String::from("WHILE_EXP0")),
+            VmCommand::Label(String::from("WHILE_END0")),
+        ];
+        let mut stats = OptimizationStats::default();
+
+        let actual = Peephole::optimize(commands, &mut stats);
+
+        assert_eq!(
+            vec![
+                VmCommand::Label(String::from("WHILE_EXP0")),
+                VmCommand::Call(String::from("Main.step"), 0),
+                VmCommand::Pop(Segment::Temp, 0),
+                VmCommand::Goto(String::from("WHILE_EXP0")),
+                VmCommand::Label(String::from("WHILE_END0")),
+            ],
+            actual
+        );
+        assert_eq!(Some(&1), stats.rewrites.get("negated-constant"));
+        assert_eq!(Some(&1), stats.rewrites.get("constant-condition"));
+    }
+
+    #[test]
+    fn can_remove_redundant_commands() {
+        let commands = vec![
+            VmCommand::Push(Segment::Local, 0),
+            VmCommand::Pop(Segment::Local, 0),
+            VmCommand::Push(Segment::Local, 1),
+            VmCommand::Arithmetic(Command::Not),
+            VmCommand::Arithmetic(Command::Not),
+            VmCommand::Pop(Segment::Local, 0),
+            VmCommand::Goto(String::from("IF_END0")),
+            VmCommand::Push(Segment::Constant, 0),
+            VmCommand::Label(String::from("IF_END0")),
+            VmCommand::Push(Segment::Constant, 0),
+            VmCommand::Return,
+            VmCommand::Push(Segment::Constant, 0),
+            VmCommand::Return,
+        ];
+        let mut stats = OptimizationStats::default();
+
+        let actual = Peephole::optimize(commands, &mut stats);
+
+        assert_eq!(
+            vec![
+                VmCommand::Push(Segment::Local, 1),
+                VmCommand::Pop(Segment::Local, 0),
+                VmCommand::Label(String::from("IF_END0")),
+                VmCommand::Push(Segment::Constant, 0),
+                VmCommand::Return,
+            ],
+            actual
+        );
+        assert_eq!(Some(&1), stats.rewrites.get("push-pop"));
+        assert_eq!(Some(&1), stats.rewrites.get("double-not"));
+        assert_eq!(Some(&3), stats.rewrites.get("unreachable"));
+        assert_eq!(Some(&1), stats.rewrites.get("jump-to-next"));
+    }
+
+    #[test]
+    fn can_reduce_strength() {
+        let multiply = VmCommand::Call(String::from("Math.multiply"), 2);
+        let commands = vec![
+            // x * 16
+            VmCommand::Push(Segment::Local, 0),
+            VmCommand::Push(Segment::Constant, 16),
+            multiply.clone(),
+            // 3 * x
+            VmCommand::Push(Segment::Constant, 3),
+            VmCommand::Push(Segment::Local, 0),
+            multiply.clone(),
+            // (x + 1) * 2
+            VmCommand::Push(Segment::Local, 0),
+            VmCommand::Push(Segment::Constant, 1),
+            VmCommand::Arithmetic(Command::Add),
+            VmCommand::Push(Segment::Constant, 2),
+            multiply.clone(),
+            // x / 1
+            VmCommand::Push(Segment::Local, 0),
+            VmCommand::Push(Segment::Constant, 1),
+            VmCommand::Call(String::from("Math.divide"), 2),
+            // x * 100
+            VmCommand::Push(Segment::Local, 0),
+            VmCommand::Push(Segment::Constant, 100),
+            multiply.clone(),
+        ];
+        let mut stats = OptimizationStats::default();
+
+        let actual = Peephole::optimize(commands, &mut stats);
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&actual, &mut output).unwrap();
+
+        let expected = "\
+push local 0
+push local 0
+add
+pop temp 0
+push temp 0
+push temp 0
+add
+pop temp 0
+push temp 0
+push temp 0
+add
+pop temp 0
+push temp 0
+push temp 0
+add
+push local 0
+push local 0
+add
+push local 0
+add
+push local 0
+push constant 1
+add
+pop temp 0
+push temp 0
+push temp 0
+add
+push local 0
+push local 0
+push constant 100
+call Math.multiply 2
+";
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+        assert_eq!(Some(&4), stats.rewrites.get("strength-reduction"));
+    }
+}
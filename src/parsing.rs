@@ -0,0 +1,18 @@
+pub mod class_parser;
+pub mod class_var_dec_parser;
+pub mod do_statement_parser;
+pub mod expression_list_parser;
+pub mod expression_parser;
+pub mod if_statement_parser;
+pub mod let_statement_parser;
+pub mod parameter_list_parser;
+pub mod precedence;
+pub mod return_statement_parser;
+pub mod statement_parser;
+pub mod statements_parser;
+pub mod subroutine_body_parser;
+pub mod subroutine_call_parser;
+pub mod subroutine_dec_parser;
+pub mod term_parser;
+pub mod var_dec_parser;
+pub mod while_statement_parser;
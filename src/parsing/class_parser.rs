@@ -0,0 +1,159 @@
+use anyhow::Result;
+
+use crate::ast::class::Class;
+use crate::ast::identifier::Identifier;
+use crate::parsing::class_var_dec_parser::ClassVarDecParser;
+use crate::parsing::precedence::Precedence;
+use crate::parsing::subroutine_dec_parser::SubroutineDecParser;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+use crate::tokenizer::key_word::KeyWord;
+
+const SUBROUTINE_KEY_WORDS: [KeyWord; 3] =
+    [KeyWord::Constructor, KeyWord::Function, KeyWord::Method];
+
+const CLASS_MEMBER_KEY_WORDS: [KeyWord; 5] = [
+    KeyWord::Static,
+    KeyWord::Field,
+    KeyWord::Constructor,
+    KeyWord::Function,
+    KeyWord::Method,
+];
+
+/// class = ’class’ className ’{’ classVarDec* subroutineDec* ’}’
+pub struct ClassParser {}
+
+impl ClassParser {
+    pub fn parse(tokenizer: &mut JackTokenizer, precedence: Precedence) -> Result<Class> {
+        // ’class’
+        tokenizer.expect_keyword(KeyWord::Class, "at start of file")?;
+        let start = *tokenizer.span();
+
+        // className
+        let name = tokenizer.expect_identifier("class name", "after `class`")?;
+        let name = Identifier::new(&name, tokenizer.span());
+
+        // {
+        tokenizer.expect_symbol('{', "after class name")?;
+
+        // classVarDec*
+        let mut class_var_decs = vec![];
+        while Self::exist_class_var_dec(tokenizer)? {
+            match ClassVarDecParser::parse(tokenizer) {
+                Ok(class_var_dec) => class_var_decs.push(class_var_dec),
+                Err(error) => {
+                    tokenizer.recover(error)?;
+                    tokenizer.synchronize(&CLASS_MEMBER_KEY_WORDS);
+                }
+            }
+        }
+
+        // subroutineDec*
+        let mut subroutine_decs = vec![];
+        while tokenizer.peek()?.value() != "}" {
+            let result = if Self::exist_subroutine_dec(tokenizer)? {
+                SubroutineDecParser::parse(tokenizer, precedence)
+            } else {
+                Err(tokenizer.unexpected("subroutine declaration or `}`"))
+            };
+
+            // report the error and resume at the next subroutine
+            match result {
+                Ok(subroutine_dec) => subroutine_decs.push(subroutine_dec),
+                Err(error) => {
+                    tokenizer.recover(error)?;
+                    tokenizer.synchronize(&SUBROUTINE_KEY_WORDS);
+                }
+            }
+        }
+
+        // }
+        tokenizer.expect_symbol('}', "at end of class")?;
+        let span = start.to(tokenizer.span());
+
+        // a file contains exactly one class
+        if tokenizer.has_more_tokens()? {
+            return Err(tokenizer.unexpected("end of file after class"));
+        }
+
+        Ok(Class {
+            name,
+            class_var_decs,
+            subroutine_decs,
+            span,
+        })
+    }
+
+    fn exist_class_var_dec(tokenizer: &JackTokenizer) -> Result<bool> {
+        if !KeyWord::exists(tokenizer.peek()?.value()) {
+            return Ok(false);
+        }
+        match KeyWord::from(tokenizer.peek()?.value())? {
+            KeyWord::Static | KeyWord::Field => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    fn exist_subroutine_dec(tokenizer: &JackTokenizer) -> Result<bool> {
+        if !KeyWord::exists(tokenizer.peek()?.value()) {
+            return Ok(false);
+        }
+        Ok(SUBROUTINE_KEY_WORDS.contains(&KeyWord::from(tokenizer.peek()?.value())?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::ast::class::{ClassVarKind, SubroutineKind};
+    use crate::error::diagnostic::Diagnostic;
+    use crate::parsing::class_parser::ClassParser;
+    use crate::parsing::precedence::Precedence;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+    #[test]
+    fn can_parse() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Main {{").unwrap();
+        writeln!(src_file, "    static int count;").unwrap();
+        writeln!(src_file, "    field boolean isTest;").unwrap();
+        writeln!(src_file, "    method void main() {{").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let actual = ClassParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+
+        assert_eq!("Main", actual.name.name);
+        assert_eq!(2, actual.class_var_decs.len());
+        assert_eq!(ClassVarKind::Field, actual.class_var_decs[1].kind);
+        assert_eq!(SubroutineKind::Method, actual.subroutine_decs[0].kind);
+        assert_eq!((1, 93), (actual.span.line, actual.span.length));
+    }
+
+    #[test]
+    fn can_report_statement_without_keyword() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Main {{").unwrap();
+        writeln!(src_file, "    function void main() {{").unwrap();
+        writeln!(src_file, "        x = 1;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let result = ClassParser::parse(&mut tokenizer, Precedence::Jack);
+        let diagnostics = tokenizer.take_diagnostics();
+        let actual: Vec<&Diagnostic> = diagnostics.iter().collect();
+
+        assert!(result.is_ok());
+        assert_eq!(1, actual.len());
+        assert_eq!("expected statement or `}`, found `x`", actual[0].message);
+        assert_eq!((3, 9), (actual[0].span.line, actual[0].span.column));
+    }
+}
@@ -0,0 +1,77 @@
+use anyhow::Result;
+
+use crate::ast::class::{ClassVarDec, ClassVarKind};
+use crate::ast::identifier::Identifier;
+use crate::ast::type_name::TypeName;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+use crate::tokenizer::key_word::KeyWord;
+
+/// classVarDec = (’static’ | ’field’) type varName (’,’ varName)* ’;’
+pub struct ClassVarDecParser {}
+
+impl ClassVarDecParser {
+    pub fn parse(tokenizer: &mut JackTokenizer) -> Result<ClassVarDec> {
+        // static or field
+        tokenizer.advance()?;
+        let start = *tokenizer.span();
+        let kind = match tokenizer.key_word()? {
+            KeyWord::Static => ClassVarKind::Static,
+            _ => ClassVarKind::Field,
+        };
+
+        // type
+        let type_name = TypeName::from(&tokenizer.expect_type("in class variable declaration")?);
+
+        // varName
+        let var_name =
+            tokenizer.expect_identifier("variable name", "in class variable declaration")?;
+        let mut var_names = vec![Identifier::new(&var_name, tokenizer.span())];
+
+        // (’,’ varName)*
+        while tokenizer.peek()?.value() == "," {
+            // ,
+            tokenizer.advance()?;
+
+            // varName
+            let var_name = tokenizer.expect_identifier("variable name", "after `,`")?;
+            var_names.push(Identifier::new(&var_name, tokenizer.span()));
+        }
+
+        // ;
+        tokenizer.expect_symbol(';', "after class variable declaration")?;
+
+        Ok(ClassVarDec {
+            kind,
+            type_name,
+            var_names,
+            span: start.to(tokenizer.span()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::ast::class::ClassVarKind;
+    use crate::ast::type_name::TypeName;
+    use crate::parsing::class_var_dec_parser::ClassVarDecParser;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+    #[test]
+    fn can_parse() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "static boolean isTest, isSomething;").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let actual = ClassVarDecParser::parse(&mut tokenizer).unwrap();
+
+        assert_eq!(ClassVarKind::Static, actual.kind);
+        assert_eq!(TypeName::Boolean, actual.type_name);
+        assert_eq!("isSomething", actual.var_names[1].name);
+        assert_eq!(35, actual.span.length);
+    }
+}
@@ -0,0 +1,28 @@
+use anyhow::Result;
+
+use crate::ast::statement::DoStatement;
+use crate::parsing::precedence::Precedence;
+use crate::parsing::subroutine_call_parser::SubroutineCallParser;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+/// doStatement = ’do’ subroutineCall ’;’
+pub struct DoStatementParser {}
+
+impl DoStatementParser {
+    pub fn parse(tokenizer: &mut JackTokenizer, precedence: Precedence) -> Result<DoStatement> {
+        // do
+        tokenizer.advance()?;
+        let start = *tokenizer.span();
+
+        // subroutineCall
+        let subroutine_call = SubroutineCallParser::parse(tokenizer, precedence)?;
+
+        // ’;’
+        tokenizer.expect_symbol(';', "after do statement")?;
+
+        Ok(DoStatement {
+            subroutine_call,
+            span: start.to(tokenizer.span()),
+        })
+    }
+}
@@ -0,0 +1,32 @@
+use anyhow::Result;
+
+use crate::ast::expression::Expression;
+use crate::parsing::expression_parser::ExpressionParser;
+use crate::parsing::precedence::Precedence;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+/// expressionList = (expression (’,’ expression)* )?
+pub struct ExpressionListParser {}
+
+impl ExpressionListParser {
+    pub fn parse(tokenizer: &mut JackTokenizer, precedence: Precedence) -> Result<Vec<Expression>> {
+        let mut expressions = vec![];
+
+        // (expression)?
+        if tokenizer.is_term()? {
+            // expression
+            expressions.push(ExpressionParser::parse(tokenizer, precedence)?);
+        }
+
+        // (’,’ expression)*
+        while tokenizer.peek()?.value() == "," {
+            // ’,’
+            tokenizer.advance()?;
+
+            // expression
+            expressions.push(ExpressionParser::parse(tokenizer, precedence)?);
+        }
+
+        Ok(expressions)
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::{Context, Result};
+
+use crate::ast::expression::{BinaryExpression, BinaryOp, Expression};
+use crate::parsing::precedence::Precedence;
+use crate::parsing::term_parser::TermParser;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+/// expression = term (op term)*
+pub struct ExpressionParser {}
+
+impl ExpressionParser {
+    pub fn parse(tokenizer: &mut JackTokenizer, precedence: Precedence) -> Result<Expression> {
+        // term
+        let lhs = Expression::Term(TermParser::parse(tokenizer, precedence)?);
+
+        // (op term)*
+        Self::parse_operations(tokenizer, precedence, lhs, 0)
+    }
+
+    /// Parses the operations following `lhs` whose operators bind at least as tightly as `min_level`,
+    /// by precedence climbing. In Jack all operators have the same level, so they are applied from left to right.
+    fn parse_operations(
+        tokenizer: &mut JackTokenizer,
+        precedence: Precedence,
+        mut lhs: Expression,
+        min_level: u8,
+    ) -> Result<Expression> {
+        while let Some(op) = Self::peek_op(tokenizer)? {
+            let level = Self::level(precedence, op);
+            if level < min_level {
+                break;
+            }
+            // op
+            tokenizer.advance()?;
+            let op_span = *tokenizer.span();
+
+            // term
+            let mut rhs = Expression::Term(TermParser::parse(tokenizer, precedence)?);
+            // operators binding tighter are applied to the term first
+            while let Some(next) = Self::peek_op(tokenizer)? {
+                if Self::level(precedence, next) <= level {
+                    break;
+                }
+                rhs = Self::parse_operations(tokenizer, precedence, rhs, level + 1)?;
+            }
+
+            let span = lhs.span().to(&rhs.span());
+            lhs = Expression::Binary(BinaryExpression {
+                op,
+                op_span,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+                span,
+            });
+        }
+
+        Ok(lhs)
+    }
+
+    fn peek_op(tokenizer: &JackTokenizer) -> Result<Option<BinaryOp>> {
+        let token = tokenizer.peek()?;
+        if !token.is_op() {
+            return Ok(None);
+        }
+        Ok(Some(
+            BinaryOp::from(token.value()).context("unknown operator")?,
+        ))
+    }
+
+    fn level(precedence: Precedence, op: BinaryOp) -> u8 {
+        match precedence {
+            Precedence::Jack => 0,
+            Precedence::Usual => op.precedence(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::ast::expression::{BinaryOp, Expression, Term};
+    use crate::parsing::expression_parser::ExpressionParser;
+    use crate::parsing::precedence::Precedence;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+    #[test]
+    fn can_parse_left_to_right() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "1 + 2 * 3;").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let actual = ExpressionParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+
+        let Expression::Binary(binary) = actual else {
+            panic!("expected binary expression");
+        };
+        assert_eq!(BinaryOp::Mul, binary.op);
+        assert!(matches!(*binary.lhs, Expression::Binary(_)));
+        assert_eq!(9, binary.span.length);
+    }
+
+    #[test]
+    fn can_parse_with_usual_precedence() {
+        fn grouping(expression: &Expression) -> String {
+            match expression {
+                Expression::Term(Term::IntegerConstant(value, _)) => value.to_string(),
+                Expression::Term(Term::Parenthesized(expression, _)) => grouping(expression),
+                Expression::Term(_) => String::from("?"),
+                Expression::Binary(binary) => format!(
+                    "({} {} {})",
+                    grouping(&binary.lhs),
+                    binary.op.symbol(),
+                    grouping(&binary.rhs)
+                ),
+            }
+        }
+
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(
+            src_file,
+            "1 | 2 + 3 * 4 - 5 < 6 & 7 = 8 / 2 * 9 | (1 + 2) * 3;"
+        )
+        .unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let actual = ExpressionParser::parse(&mut tokenizer, Precedence::Usual).unwrap();
+
+        assert_eq!(
+            "((1 | ((((2 + (3 * 4)) - 5) < 6) & (7 = ((8 / 2) * 9)))) | ((1 + 2) * 3))",
+            grouping(&actual)
+        );
+        assert_eq!(";", tokenizer.peek().unwrap().value());
+    }
+}
@@ -0,0 +1,90 @@
+use anyhow::Result;
+
+use crate::ast::statement::IfStatement;
+use crate::parsing::expression_parser::ExpressionParser;
+use crate::parsing::precedence::Precedence;
+use crate::parsing::statements_parser::StatementsParser;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+use crate::tokenizer::key_word::KeyWord;
+use crate::tokenizer::token_type::TokenType::Keyword;
+
+/// ifStatement = ’if’ ’(’ expression ’)’ ’{’ statements ’}’ (’else’ ’{’ statements ’}’)?
+pub struct IfStatementParser {}
+
+impl IfStatementParser {
+    pub fn parse(tokenizer: &mut JackTokenizer, precedence: Precedence) -> Result<IfStatement> {
+        // if
+        tokenizer.advance()?;
+        let start = *tokenizer.span();
+
+        // ’(’
+        tokenizer.expect_symbol('(', "after `if`")?;
+
+        // expression
+        let condition = ExpressionParser::parse(tokenizer, precedence)?;
+
+        // ’)’
+        tokenizer.expect_symbol(')', "after if condition")?;
+
+        // ’{’
+        tokenizer.expect_symbol('{', "at start of if body")?;
+
+        // statements
+        let statements = StatementsParser::parse(tokenizer, precedence)?;
+
+        // ’}’
+        tokenizer.expect_symbol('}', "at end of if body")?;
+
+        // (’else’ ’{’ statements ’}’)?
+        let else_statements = if tokenizer.peek()?.token_type() == &Keyword
+            && KeyWord::from(tokenizer.peek()?.value())? == KeyWord::Else
+        {
+            // else
+            tokenizer.advance()?;
+            // ’{’
+            tokenizer.expect_symbol('{', "after `else`")?;
+            // statements
+            let else_statements = StatementsParser::parse(tokenizer, precedence)?;
+            // ’}’
+            tokenizer.expect_symbol('}', "at end of else body")?;
+            Some(else_statements)
+        } else {
+            None
+        };
+
+        Ok(IfStatement {
+            condition,
+            statements,
+            else_statements,
+            span: start.to(tokenizer.span()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::parsing::if_statement_parser::IfStatementParser;
+    use crate::parsing::precedence::Precedence;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+    #[test]
+    fn can_parse_if_else() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "if (x) {{").unwrap();
+        writeln!(src_file, "    let y = 1;").unwrap();
+        writeln!(src_file, "}} else {{").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let actual = IfStatementParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+
+        assert_eq!(1, actual.statements.len());
+        assert_eq!(Some(0), actual.else_statements.map(|s| s.len()));
+        assert_eq!(34, actual.span.length);
+    }
+}
@@ -0,0 +1,105 @@
+use anyhow::Result;
+
+use crate::ast::identifier::Identifier;
+use crate::ast::statement::LetStatement;
+use crate::parsing::expression_parser::ExpressionParser;
+use crate::parsing::precedence::Precedence;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+/// letStatement = ’let’ varName (’[’ expression ’]’)? ’=’ expression ’;’
+pub struct LetStatementParser {}
+
+impl LetStatementParser {
+    pub fn parse(tokenizer: &mut JackTokenizer, precedence: Precedence) -> Result<LetStatement> {
+        // ’let’
+        tokenizer.advance()?;
+        let start = *tokenizer.span();
+
+        // varName
+        let var_name = tokenizer.expect_identifier("variable name", "after `let`")?;
+        let var_name = Identifier::new(&var_name, tokenizer.span());
+
+        // (’[’ expression ’]’)?
+        let index = if tokenizer.peek()?.value() == "[" {
+            // ’[’
+            tokenizer.advance()?;
+            // expression
+            let index = ExpressionParser::parse(tokenizer, precedence)?;
+            // ’]’
+            tokenizer.expect_symbol(']', "after array index")?;
+            Some(index)
+        } else {
+            None
+        };
+
+        // ’=’
+        tokenizer.expect_symbol('=', "in let statement")?;
+
+        // expression
+        let value = ExpressionParser::parse(tokenizer, precedence)?;
+
+        // ’;’
+        tokenizer.expect_symbol(';', "after let statement")?;
+
+        Ok(LetStatement {
+            var_name,
+            index,
+            value,
+            span: start.to(tokenizer.span()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::error::diagnostic::Diagnostic;
+    use crate::parsing::let_statement_parser::LetStatementParser;
+    use crate::parsing::precedence::Precedence;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+    #[test]
+    fn can_parse_array_element() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(
+            src_file,
+            "let a[i] = Keyboard.readInt(\"ENTER THE NEXT NUMBER: \");"
+        )
+        .unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let actual = LetStatementParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+
+        assert_eq!("a", actual.var_name.name);
+        assert!(actual.index.is_some());
+        assert_eq!(55, actual.span.length);
+    }
+
+    #[test]
+    fn can_report_missing_semicolon() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(
+            src_file,
+            "let length = Keyboard.readInt(\"HOW MANY NUMBERS? \")"
+        )
+        .unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let result = LetStatementParser::parse(&mut tokenizer, Precedence::Jack);
+        let actual = result.unwrap_err().downcast::<Diagnostic>().unwrap();
+
+        assert_eq!(
+            "expected `;` after let statement, found `}`",
+            actual.message
+        );
+        assert_eq!((2, 1), (actual.span.line, actual.span.column));
+    }
+}
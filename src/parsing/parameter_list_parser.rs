@@ -0,0 +1,68 @@
+use anyhow::Result;
+
+use crate::ast::class::Parameter;
+use crate::ast::identifier::Identifier;
+use crate::ast::type_name::TypeName;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+/// parameterList = ((type varName) (’,’ type varName)*)?
+pub struct ParameterListParser {}
+
+impl ParameterListParser {
+    pub fn parse(tokenizer: &mut JackTokenizer) -> Result<Vec<Parameter>> {
+        let mut parameters = vec![];
+
+        // (type varName)?
+        if tokenizer.peek()?.is_type()? {
+            parameters.push(Self::parse_parameter(tokenizer)?);
+        }
+
+        // (’,’ type varName)*
+        while tokenizer.peek()?.value() == "," {
+            // ’,’
+            tokenizer.advance()?;
+
+            parameters.push(Self::parse_parameter(tokenizer)?);
+        }
+        Ok(parameters)
+    }
+
+    fn parse_parameter(tokenizer: &mut JackTokenizer) -> Result<Parameter> {
+        // type
+        let type_name = TypeName::from(&tokenizer.expect_type("in parameter list")?);
+
+        // varName
+        let var_name = tokenizer.expect_identifier("parameter name", "after parameter type")?;
+        let var_name = Identifier::new(&var_name, tokenizer.span());
+
+        Ok(Parameter {
+            type_name,
+            var_name,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::ast::type_name::TypeName;
+    use crate::parsing::parameter_list_parser::ParameterListParser;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+    #[test]
+    fn can_parse() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "int count, boolean isTest, char c)").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let actual = ParameterListParser::parse(&mut tokenizer).unwrap();
+
+        assert_eq!(3, actual.len());
+        assert_eq!(TypeName::Char, actual[2].type_name);
+        assert_eq!("isTest", actual[1].var_name.name);
+    }
+}
@@ -0,0 +1,11 @@
+use clap::ValueEnum;
+
+/// How operators without parentheses are grouped.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Precedence {
+    /// From left to right, as the Jack language specifies
+    #[default]
+    Jack,
+    /// `*` `/` before `+` `-` before comparisons before `&` before `|` before `&&` before `||`
+    Usual,
+}
@@ -0,0 +1,34 @@
+use anyhow::Result;
+
+use crate::ast::statement::ReturnStatement;
+use crate::parsing::expression_parser::ExpressionParser;
+use crate::parsing::precedence::Precedence;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+/// returnStatement = ’return’ expression? ’;’
+pub struct ReturnStatementParser {}
+
+impl ReturnStatementParser {
+    pub fn parse(tokenizer: &mut JackTokenizer, precedence: Precedence) -> Result<ReturnStatement> {
+        // return
+        tokenizer.advance()?;
+        let start = *tokenizer.span();
+
+        // expression?
+        // a `}` means the `;` is missing rather than the expression
+        let next = tokenizer.peek()?.value();
+        let value = if next != ";" && next != "}" {
+            Some(ExpressionParser::parse(tokenizer, precedence)?)
+        } else {
+            None
+        };
+
+        // ’;’
+        tokenizer.expect_symbol(';', "after return statement")?;
+
+        Ok(ReturnStatement {
+            value,
+            span: start.to(tokenizer.span()),
+        })
+    }
+}
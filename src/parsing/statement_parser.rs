@@ -0,0 +1,30 @@
+use anyhow::Result;
+
+use crate::ast::statement::Statement;
+use crate::parsing::do_statement_parser::DoStatementParser;
+use crate::parsing::if_statement_parser::IfStatementParser;
+use crate::parsing::let_statement_parser::LetStatementParser;
+use crate::parsing::precedence::Precedence;
+use crate::parsing::return_statement_parser::ReturnStatementParser;
+use crate::parsing::while_statement_parser::WhileStatementParser;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+use crate::tokenizer::key_word::KeyWord;
+
+/// statement = letStatement | ifStatement | whileStatement | doStatement | returnStatement
+pub struct StatementParser {}
+
+impl StatementParser {
+    pub fn parse(tokenizer: &mut JackTokenizer, precedence: Precedence) -> Result<Statement> {
+        let statement = match KeyWord::from(tokenizer.peek()?.value())? {
+            KeyWord::Let => Statement::Let(LetStatementParser::parse(tokenizer, precedence)?),
+            KeyWord::If => Statement::If(IfStatementParser::parse(tokenizer, precedence)?),
+            KeyWord::While => Statement::While(WhileStatementParser::parse(tokenizer, precedence)?),
+            KeyWord::Do => Statement::Do(DoStatementParser::parse(tokenizer, precedence)?),
+            KeyWord::Return => {
+                Statement::Return(ReturnStatementParser::parse(tokenizer, precedence)?)
+            }
+            _ => return Err(tokenizer.unexpected("statement")),
+        };
+        Ok(statement)
+    }
+}
@@ -0,0 +1,81 @@
+use anyhow::Result;
+
+use crate::ast::statement::Statement;
+use crate::parsing::precedence::Precedence;
+use crate::parsing::statement_parser::StatementParser;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+use crate::tokenizer::key_word::KeyWord;
+use crate::tokenizer::token_type::TokenType;
+
+/// Keywords that start a statement, where parsing can resume after a syntax error.
+pub const STATEMENT_KEY_WORDS: [KeyWord; 5] = [
+    KeyWord::Let,
+    KeyWord::If,
+    KeyWord::While,
+    KeyWord::Do,
+    KeyWord::Return,
+];
+
+/// statements = statement*
+pub struct StatementsParser {}
+
+impl StatementsParser {
+    pub fn parse(tokenizer: &mut JackTokenizer, precedence: Precedence) -> Result<Vec<Statement>> {
+        let mut statements = vec![];
+
+        // statements are always closed by ’}’
+        while tokenizer.peek()?.value() != "}" {
+            let result = if Self::exist_statement(tokenizer)? {
+                StatementParser::parse(tokenizer, precedence)
+            } else {
+                Err(tokenizer.unexpected("statement or `}`"))
+            };
+
+            // report the error and resume at the next statement
+            match result {
+                Ok(statement) => statements.push(statement),
+                Err(error) => {
+                    tokenizer.recover(error)?;
+                    tokenizer.synchronize(&STATEMENT_KEY_WORDS);
+                }
+            }
+        }
+        Ok(statements)
+    }
+
+    fn exist_statement(tokenizer: &JackTokenizer) -> Result<bool> {
+        let token = tokenizer.peek()?;
+        if token.token_type() != &TokenType::Keyword {
+            return Ok(false);
+        }
+        Ok(STATEMENT_KEY_WORDS.contains(&KeyWord::from(token.value())?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::ast::statement::Statement;
+    use crate::parsing::precedence::Precedence;
+    use crate::parsing::statements_parser::StatementsParser;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+    #[test]
+    fn can_skip_broken_statement() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "let x = ;").unwrap();
+        writeln!(src_file, "do Output.printInt(x);").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let actual = StatementsParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+
+        assert_eq!(1, actual.len());
+        assert!(matches!(actual[0], Statement::Do(_)));
+        assert_eq!(1, tokenizer.take_diagnostics().len());
+    }
+}
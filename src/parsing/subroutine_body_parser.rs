@@ -0,0 +1,81 @@
+use anyhow::Result;
+
+use crate::ast::class::SubroutineBody;
+use crate::parsing::precedence::Precedence;
+use crate::parsing::statements_parser::StatementsParser;
+use crate::parsing::var_dec_parser::VarDecParser;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+use crate::tokenizer::key_word::KeyWord;
+
+/// subroutineBody = ’{’ varDec* statements ’}’
+pub struct SubroutineBodyParser {}
+
+impl SubroutineBodyParser {
+    pub fn parse(tokenizer: &mut JackTokenizer, precedence: Precedence) -> Result<SubroutineBody> {
+        // ’{’
+        tokenizer.expect_symbol('{', "at start of subroutine body")?;
+        let start = *tokenizer.span();
+
+        // varDec*
+        let mut var_decs = vec![];
+        while KeyWord::exists(tokenizer.peek()?.value())
+            && KeyWord::from(tokenizer.peek()?.value())? == KeyWord::Var
+        {
+            match VarDecParser::parse(tokenizer) {
+                Ok(var_dec) => var_decs.push(var_dec),
+                Err(error) => {
+                    tokenizer.recover(error)?;
+                    tokenizer.synchronize(&[
+                        KeyWord::Var,
+                        KeyWord::Let,
+                        KeyWord::If,
+                        KeyWord::While,
+                        KeyWord::Do,
+                        KeyWord::Return,
+                    ]);
+                }
+            }
+        }
+
+        // statements
+        let statements = StatementsParser::parse(tokenizer, precedence)?;
+
+        // ’}’
+        tokenizer.expect_symbol('}', "at end of subroutine body")?;
+
+        Ok(SubroutineBody {
+            var_decs,
+            statements,
+            span: start.to(tokenizer.span()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::parsing::precedence::Precedence;
+    use crate::parsing::subroutine_body_parser::SubroutineBodyParser;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+    #[test]
+    fn can_recover_from_broken_var_dec() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "{{").unwrap();
+        writeln!(src_file, "    var int mask position;").unwrap();
+        writeln!(src_file, "    var boolean loop;").unwrap();
+        writeln!(src_file, "    return;").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let actual = SubroutineBodyParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+
+        assert_eq!(1, actual.var_decs.len());
+        assert_eq!(1, actual.statements.len());
+        assert_eq!(1, tokenizer.take_diagnostics().len());
+    }
+}
@@ -0,0 +1,78 @@
+use anyhow::Result;
+
+use crate::ast::expression::SubroutineCall;
+use crate::ast::identifier::Identifier;
+use crate::parsing::expression_list_parser::ExpressionListParser;
+use crate::parsing::precedence::Precedence;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+/// subroutineCall = subroutineName ’(’ expressionList ’)’ | (className | varName) ’.’ subroutineName ’(’ expressionList ’)’
+pub struct SubroutineCallParser {}
+
+impl SubroutineCallParser {
+    pub fn parse(tokenizer: &mut JackTokenizer, precedence: Precedence) -> Result<SubroutineCall> {
+        // subroutineName | (className | varName)
+        let name = tokenizer.expect_identifier("subroutine name", "in subroutine call")?;
+        let name = Identifier::new(&name, tokenizer.span());
+        let start = name.span;
+
+        let (receiver, subroutine_name) = if tokenizer.peek()?.value() == "." {
+            // ’.’
+            tokenizer.advance()?;
+
+            // subroutineName
+            let subroutine_name = tokenizer.expect_identifier("subroutine name", "after `.`")?;
+            (
+                Some(name),
+                Identifier::new(&subroutine_name, tokenizer.span()),
+            )
+        } else {
+            (None, name)
+        };
+
+        // ’(’
+        tokenizer.expect_symbol('(', "after subroutine name")?;
+
+        // expressionList
+        let arguments = ExpressionListParser::parse(tokenizer, precedence)?;
+
+        // ’)’
+        tokenizer.expect_symbol(')', "after argument list")?;
+
+        Ok(SubroutineCall {
+            receiver,
+            subroutine_name,
+            arguments,
+            span: start.to(tokenizer.span()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::parsing::precedence::Precedence;
+    use crate::parsing::subroutine_call_parser::SubroutineCallParser;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+    #[test]
+    fn can_parse_method_call() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "game.run(1, x);").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let actual = SubroutineCallParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+
+        assert_eq!(
+            Some("game"),
+            actual.receiver.as_ref().map(|r| r.name.as_str())
+        );
+        assert_eq!("run", actual.subroutine_name.name);
+        assert_eq!(2, actual.arguments.len());
+        assert_eq!(14, actual.span.length);
+    }
+}
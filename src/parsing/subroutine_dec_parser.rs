@@ -0,0 +1,111 @@
+use anyhow::Result;
+
+use crate::ast::class::{SubroutineDec, SubroutineKind};
+use crate::ast::identifier::Identifier;
+use crate::ast::type_name::TypeName;
+use crate::parsing::parameter_list_parser::ParameterListParser;
+use crate::parsing::precedence::Precedence;
+use crate::parsing::subroutine_body_parser::SubroutineBodyParser;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+use crate::tokenizer::key_word::KeyWord;
+
+/// subroutineDec =(’constructor’ | ’function’ | ’method’) (’void’ | type) subroutineName ’(’ parameterList ’)’ subroutineBody
+pub struct SubroutineDecParser {}
+
+impl SubroutineDecParser {
+    pub fn parse(tokenizer: &mut JackTokenizer, precedence: Precedence) -> Result<SubroutineDec> {
+        // ’constructor’ | ’function’ | ’method’
+        tokenizer.advance()?;
+        let start = *tokenizer.span();
+        let kind = match tokenizer.key_word()? {
+            KeyWord::Constructor => SubroutineKind::Constructor,
+            KeyWord::Function => SubroutineKind::Function,
+            _ => SubroutineKind::Method,
+        };
+
+        // ’void’ | type
+        let return_type = if tokenizer.peek()?.value() == "void" {
+            tokenizer.advance()?;
+            None
+        } else {
+            let type_name = tokenizer.expect_type(&format!("or `void` after `{kind}`"))?;
+            Some(TypeName::from(&type_name))
+        };
+
+        // subroutineName
+        let name = tokenizer.expect_identifier("subroutine name", "after return type")?;
+        let name = Identifier::new(&name, tokenizer.span());
+
+        // ’(’
+        tokenizer.expect_symbol('(', "after subroutine name")?;
+        // parameterList
+        let parameters = ParameterListParser::parse(tokenizer)?;
+        // ’)’
+        tokenizer.expect_symbol(')', "after parameter list")?;
+
+        // subroutineBody
+        let body = SubroutineBodyParser::parse(tokenizer, precedence)?;
+
+        Ok(SubroutineDec {
+            kind,
+            return_type,
+            name,
+            parameters,
+            span: start.to(&body.span),
+            body,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::ast::class::SubroutineKind;
+    use crate::ast::type_name::TypeName;
+    use crate::error::diagnostic::Diagnostic;
+    use crate::parsing::precedence::Precedence;
+    use crate::parsing::subroutine_dec_parser::SubroutineDecParser;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+    #[test]
+    fn can_parse() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "constructor Square new(int ax, int ay) {{").unwrap();
+        writeln!(src_file, "    return this;").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let actual = SubroutineDecParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+
+        assert_eq!(SubroutineKind::Constructor, actual.kind);
+        assert_eq!(
+            Some(TypeName::Class(String::from("Square"))),
+            actual.return_type
+        );
+        assert_eq!("new", actual.name.name);
+        assert_eq!(2, actual.parameters.len());
+        assert_eq!(1, actual.body.statements.len());
+    }
+
+    #[test]
+    fn can_report_missing_return_type() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "function main() {{").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let result = SubroutineDecParser::parse(&mut tokenizer, Precedence::Jack);
+        let actual = result.unwrap_err().downcast::<Diagnostic>().unwrap();
+
+        assert_eq!(
+            "expected subroutine name after return type, found `(`",
+            actual.message
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use anyhow::Result;
+
+use crate::ast::expression::{KeywordConstant, Term, UnaryOp};
+use crate::ast::identifier::Identifier;
+use crate::parsing::expression_parser::ExpressionParser;
+use crate::parsing::precedence::Precedence;
+use crate::parsing::subroutine_call_parser::SubroutineCallParser;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+use crate::tokenizer::key_word::KeyWord;
+use crate::tokenizer::token_type::TokenType;
+
+/// term = integerConstant | stringConstant | keywordConstant | varName | varName ’[’ expression ’]’ | subroutineCall | ’(’ expression ’)’ | unaryOp term
+pub struct TermParser {}
+
+impl TermParser {
+    pub fn parse(tokenizer: &mut JackTokenizer, precedence: Precedence) -> Result<Term> {
+        match tokenizer.peek()?.token_type() {
+            TokenType::Keyword => {
+                if !tokenizer.peek()?.is_keyword_constant()? {
+                    return Err(tokenizer.unexpected("expression"));
+                }
+                tokenizer.advance()?;
+                let keyword_constant = match tokenizer.key_word()? {
+                    KeyWord::True => KeywordConstant::True,
+                    KeyWord::False => KeywordConstant::False,
+                    KeyWord::Null => KeywordConstant::Null,
+                    _ => KeywordConstant::This,
+                };
+                Ok(Term::KeywordConstant(keyword_constant, *tokenizer.span()))
+            }
+            TokenType::Symbol => match tokenizer.peek()?.value().as_str() {
+                "(" => {
+                    // '('
+                    tokenizer.advance()?;
+                    let start = *tokenizer.span();
+                    // expression
+                    let expression = ExpressionParser::parse(tokenizer, precedence)?;
+                    // ')'
+                    tokenizer.expect_symbol(')', "after expression")?;
+                    Ok(Term::Parenthesized(
+                        Box::new(expression),
+                        start.to(tokenizer.span()),
+                    ))
+                }
+                "-" | "~" => {
+                    // unaryOp
+                    tokenizer.advance()?;
+                    let start = *tokenizer.span();
+                    let op = match tokenizer.symbol() {
+                        '-' => UnaryOp::Neg,
+                        _ => UnaryOp::Not,
+                    };
+                    // term
+                    let term = TermParser::parse(tokenizer, precedence)?;
+                    let span = start.to(&term.span());
+                    Ok(Term::Unary(op, Box::new(term), span))
+                }
+                _ => Err(tokenizer.unexpected("expression")),
+            },
+            TokenType::Identifier => match tokenizer.peek_second()?.value().as_str() {
+                "[" => {
+                    // varName
+                    tokenizer.advance()?;
+                    let var_name = Identifier::new(tokenizer.identifier(), tokenizer.span());
+                    // '['
+                    tokenizer.advance()?;
+                    // expression
+                    let index = ExpressionParser::parse(tokenizer, precedence)?;
+                    // ']'
+                    tokenizer.expect_symbol(']', "after array index")?;
+                    let span = var_name.span.to(tokenizer.span());
+                    Ok(Term::ArrayElement(var_name, Box::new(index), span))
+                }
+                "." | "(" => {
+                    let subroutine_call = SubroutineCallParser::parse(tokenizer, precedence)?;
+                    Ok(Term::SubroutineCall(Box::new(subroutine_call)))
+                }
+                _ => {
+                    // varName
+                    tokenizer.advance()?;
+                    Ok(Term::VarName(Identifier::new(
+                        tokenizer.identifier(),
+                        tokenizer.span(),
+                    )))
+                }
+            },
+            TokenType::IntConst => {
+                tokenizer.advance()?;
+                let value = tokenizer.int_val()?;
+                let span = *tokenizer.span();
+                if tokenizer.is_bit_pattern() {
+                    Ok(Term::BitPattern(value, span))
+                } else {
+                    Ok(Term::IntegerConstant(value, span))
+                }
+            }
+            TokenType::CharConst => {
+                tokenizer.advance()?;
+                Ok(Term::CharConstant(tokenizer.char_val(), *tokenizer.span()))
+            }
+            TokenType::StringConst => {
+                tokenizer.advance()?;
+                Ok(Term::StringConstant(
+                    String::from(tokenizer.string_val()),
+                    *tokenizer.span(),
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::ast::expression::{Term, UnaryOp};
+    use crate::error::diagnostic::Diagnostic;
+    use crate::parsing::precedence::Precedence;
+    use crate::parsing::term_parser::TermParser;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+    #[test]
+    fn can_parse_unary_op() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "~(x = 1)").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let actual = TermParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+
+        assert!(matches!(actual, Term::Unary(UnaryOp::Not, _, _)));
+        assert_eq!(8, actual.span().length);
+    }
+
+    #[test]
+    fn can_report_missing_expression() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, ")").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let result = TermParser::parse(&mut tokenizer, Precedence::Jack);
+        let actual = result.unwrap_err().downcast::<Diagnostic>().unwrap();
+
+        assert_eq!("expected expression, found `)`", actual.message);
+    }
+}
@@ -0,0 +1,75 @@
+use anyhow::Result;
+
+use crate::ast::class::VarDec;
+use crate::ast::identifier::Identifier;
+use crate::ast::type_name::TypeName;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+use crate::tokenizer::token_type::TokenType::Symbol;
+
+/// varDec = ’var’ type varName (’,’ varName)* ’;’
+pub struct VarDecParser {}
+
+impl VarDecParser {
+    pub fn parse(tokenizer: &mut JackTokenizer) -> Result<VarDec> {
+        // ’var’
+        tokenizer.advance()?;
+        let start = *tokenizer.span();
+
+        // type
+        let type_name = TypeName::from(&tokenizer.expect_type("in variable declaration")?);
+
+        // varName
+        let var_name = tokenizer.expect_identifier("variable name", "in variable declaration")?;
+        let mut var_names = vec![Identifier::new(&var_name, tokenizer.span())];
+
+        // (’,’ varName)*
+        while tokenizer.peek()?.token_type() == &Symbol && tokenizer.peek()?.value() == "," {
+            // ','
+            tokenizer.advance()?;
+
+            // varName
+            let var_name = tokenizer.expect_identifier("variable name", "after `,`")?;
+            var_names.push(Identifier::new(&var_name, tokenizer.span()));
+        }
+
+        // ’;’
+        tokenizer.expect_symbol(';', "after variable declaration")?;
+
+        Ok(VarDec {
+            type_name,
+            var_names,
+            span: start.to(tokenizer.span()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::ast::type_name::TypeName;
+    use crate::parsing::var_dec_parser::VarDecParser;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+    #[test]
+    fn can_parse() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "var Array i, j, sum;").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let actual = VarDecParser::parse(&mut tokenizer).unwrap();
+
+        assert_eq!(TypeName::Class(String::from("Array")), actual.type_name);
+        assert_eq!(3, actual.var_names.len());
+        assert_eq!(
+            (1, 17),
+            (
+                actual.var_names[2].span.line,
+                actual.var_names[2].span.column
+            )
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use anyhow::Result;
+
+use crate::ast::statement::WhileStatement;
+use crate::parsing::expression_parser::ExpressionParser;
+use crate::parsing::precedence::Precedence;
+use crate::parsing::statements_parser::StatementsParser;
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+
+/// whileStatement = ’while’ ’(’ expression ’)’ ’{’ statements ’}’
+pub struct WhileStatementParser {}
+
+impl WhileStatementParser {
+    pub fn parse(tokenizer: &mut JackTokenizer, precedence: Precedence) -> Result<WhileStatement> {
+        // while
+        tokenizer.advance()?;
+        let start = *tokenizer.span();
+
+        // ’(’
+        tokenizer.expect_symbol('(', "after `while`")?;
+
+        // expression
+        let condition = ExpressionParser::parse(tokenizer, precedence)?;
+
+        // ’)’
+        tokenizer.expect_symbol(')', "after while condition")?;
+        // ’{’
+        tokenizer.expect_symbol('{', "at start of while body")?;
+
+        // statements
+        let statements = StatementsParser::parse(tokenizer, precedence)?;
+
+        // ’}’
+        tokenizer.expect_symbol('}', "at end of while body")?;
+
+        Ok(WhileStatement {
+            condition,
+            statements,
+            span: start.to(tokenizer.span()),
+        })
+    }
+}
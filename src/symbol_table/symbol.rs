@@ -1,18 +1,21 @@
 use crate::symbol_table::kind::Kind;
+use crate::tokenizer::span::Span;
 
 #[derive(Debug, PartialEq)]
 pub struct Symbol {
     pub type_name: String,
     pub kind: Kind,
     pub index: usize,
+    pub span: Span,
 }
 
 impl Symbol {
-    pub fn new(type_name: &str, kind: &Kind, index: usize) -> Self {
+    pub fn new(type_name: &str, kind: &Kind, index: usize, span: &Span) -> Self {
         Symbol {
             type_name: String::from(type_name),
             kind: Kind::from(kind),
             index,
+            span: *span,
         }
     }
 }
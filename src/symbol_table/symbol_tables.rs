@@ -1,7 +1,11 @@
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 
+use anyhow::{Context, Result};
+
 use crate::symbol_table::kind::Kind;
 use crate::symbol_table::symbol::Symbol;
+use crate::tokenizer::span::Span;
 
 pub struct SymbolTables {
     class_table: HashMap<String, Symbol>,
@@ -22,21 +26,34 @@ impl SymbolTables {
         self.subroutine_table = Default::default()
     }
 
-    pub fn define(&mut self, name: &str, type_name: &str, kind: &Kind) {
-        match kind {
-            Kind::Static | Kind::Field => {
-                let index = self.var_count(Kind::from(kind));
-                self.class_table
-                    .insert(String::from(name), Symbol::new(type_name, kind, index));
-            }
-            Kind::Argument | Kind::Var => {
-                let index = self.var_count(Kind::from(kind));
-                self.subroutine_table
-                    .insert(String::from(name), Symbol::new(type_name, kind, index));
+    /// Defines a symbol in the scope of its kind.
+    /// When the name is already taken in that scope, nothing is defined and the earlier symbol is returned.
+    pub fn define(
+        &mut self,
+        name: &str,
+        type_name: &str,
+        kind: &Kind,
+        span: &Span,
+    ) -> Option<&Symbol> {
+        let index = self.var_count(Kind::from(kind));
+        let table = match kind {
+            Kind::Static | Kind::Field => &mut self.class_table,
+            Kind::Argument | Kind::Var => &mut self.subroutine_table,
+        };
+        match table.entry(String::from(name)) {
+            Entry::Occupied(entry) => Some(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                entry.insert(Symbol::new(type_name, kind, index, span));
+                None
             }
         }
     }
 
+    /// Gets a static or field, even when a local or argument hides it.
+    pub fn get_class_symbol(&self, name: &str) -> Option<&Symbol> {
+        self.class_table.get(name)
+    }
+
     pub fn var_count(&mut self, kind: Kind) -> usize {
         match kind {
             Kind::Static | Kind::Field => {
@@ -60,6 +77,17 @@ impl SymbolTables {
         }
     }
 
+    /// Gets the symbol of a variable that must have been declared.
+    pub fn resolve(&mut self, name: &str) -> Result<&Symbol> {
+        self.get(name)
+            .with_context(|| format!("undefined variable `{name}`"))
+    }
+
+    /// Returns the names visible in the current subroutine.
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        self.subroutine_table.keys().chain(self.class_table.keys())
+    }
+
     #[allow(dead_code)]
     pub fn kind_of(&mut self, name: &str) -> Option<&Kind> {
         match self.subroutine_table.get(name) {
@@ -71,7 +99,6 @@ impl SymbolTables {
         }
     }
 
-    #[allow(dead_code)]
     pub fn type_of(&mut self, name: &str) -> Option<String> {
         match self.subroutine_table.get(name) {
             Some(s) => Some(String::from(&s.type_name)),
@@ -96,11 +123,12 @@ mod tests {
     use crate::symbol_table::kind::Kind;
     use crate::symbol_table::symbol::Symbol;
     use crate::symbol_table::symbol_tables::SymbolTables;
+    use crate::tokenizer::span::Span;
 
     #[test]
     fn can_start_subroutine() {
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("is_test", "boolean", &Kind::Var);
+        symbol_tables.define("is_test", "boolean", &Kind::Var, &Span::default());
         symbol_tables.start_subroutine();
         let actual = symbol_tables.subroutine_table.len();
         assert_eq!(0, actual);
@@ -109,24 +137,49 @@ mod tests {
     #[test]
     fn can_define_class_scope_symbol() {
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("is_test", "boolean", &Kind::Static);
+        symbol_tables.define("is_test", "boolean", &Kind::Static, &Span::default());
         let actual = symbol_tables.class_table.get("is_test").unwrap();
-        assert_eq!(Symbol::new("boolean", &Kind::Static, 0), *actual);
+        assert_eq!(
+            Symbol::new("boolean", &Kind::Static, 0, &Span::default()),
+            *actual
+        );
     }
 
     #[test]
     fn can_define_subroutine_scope_symbol() {
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("is_test", "boolean", &Kind::Var);
+        symbol_tables.define("is_test", "boolean", &Kind::Var, &Span::default());
         let actual = symbol_tables.subroutine_table.get("is_test").unwrap();
-        assert_eq!(Symbol::new("boolean", &Kind::Var, 0), *actual);
+        assert_eq!(
+            Symbol::new("boolean", &Kind::Var, 0, &Span::default()),
+            *actual
+        );
+    }
+
+    #[test]
+    fn can_keep_first_of_duplicate_symbols() {
+        let mut symbol_tables = SymbolTables::new();
+        let first = Span::new(0, 10, 2, 9, 1);
+        symbol_tables.define("x", "int", &Kind::Var, &first);
+        let actual = symbol_tables.define("x", "boolean", &Kind::Var, &Span::default());
+        assert_eq!(Some(&Symbol::new("int", &Kind::Var, 0, &first)), actual);
+        assert_eq!(1, symbol_tables.var_count(Kind::Var));
+    }
+
+    #[test]
+    fn can_define_local_with_name_of_field() {
+        let mut symbol_tables = SymbolTables::new();
+        symbol_tables.define("x", "int", &Kind::Field, &Span::default());
+        let actual = symbol_tables.define("x", "int", &Kind::Var, &Span::default());
+        assert!(actual.is_none());
+        assert_eq!(&Kind::Var, symbol_tables.kind_of("x").unwrap());
     }
 
     #[test]
     fn can_count_number_of_kind() {
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("count", "int", &Kind::Var);
-        symbol_tables.define("is_test", "boolean", &Kind::Var);
+        symbol_tables.define("count", "int", &Kind::Var, &Span::default());
+        symbol_tables.define("is_test", "boolean", &Kind::Var, &Span::default());
         let actual = symbol_tables.var_count(Kind::Var);
         assert_eq!(2, actual);
     }
@@ -134,7 +187,7 @@ mod tests {
     #[test]
     fn can_get_kind_of_subroutine_scope_from_name() {
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("is_test", "boolean", &Kind::Argument);
+        symbol_tables.define("is_test", "boolean", &Kind::Argument, &Span::default());
         let actual = symbol_tables.kind_of("is_test").unwrap();
         assert_eq!(Kind::Argument, *actual);
     }
@@ -142,7 +195,7 @@ mod tests {
     #[test]
     fn can_get_kind_of_class_scope_from_name() {
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("is_test", "boolean", &Kind::Field);
+        symbol_tables.define("is_test", "boolean", &Kind::Field, &Span::default());
         let actual = symbol_tables.kind_of("is_test").unwrap();
         assert_eq!(Kind::Field, *actual)
     }
@@ -150,7 +203,7 @@ mod tests {
     #[test]
     fn can_get_kind_of_none() {
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("is_test", "boolean", &Kind::Field);
+        symbol_tables.define("is_test", "boolean", &Kind::Field, &Span::default());
         let actual = symbol_tables.kind_of("hoge");
         assert!(actual.is_none());
     }
@@ -158,7 +211,7 @@ mod tests {
     #[test]
     fn can_get_type_of_subroutine_scope_from_name() {
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("is_test", "boolean", &Kind::Argument);
+        symbol_tables.define("is_test", "boolean", &Kind::Argument, &Span::default());
         let actual = symbol_tables.type_of("is_test").unwrap();
         assert_eq!("boolean", actual);
     }
@@ -166,7 +219,7 @@ mod tests {
     #[test]
     fn can_get_type_of_class_scope_from_name() {
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("is_test", "boolean", &Kind::Field);
+        symbol_tables.define("is_test", "boolean", &Kind::Field, &Span::default());
         let actual = symbol_tables.type_of("is_test").unwrap();
         assert_eq!("boolean", actual)
     }
@@ -174,7 +227,7 @@ mod tests {
     #[test]
     fn can_get_index_of_subroutine_scope_from_name() {
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("is_test", "boolean", &Kind::Argument);
+        symbol_tables.define("is_test", "boolean", &Kind::Argument, &Span::default());
         let actual = symbol_tables.index_of("is_test").unwrap();
         assert_eq!(0, actual);
     }
@@ -182,7 +235,7 @@ mod tests {
     #[test]
     fn can_get_index_of_class_scope_from_name() {
         let mut symbol_tables = SymbolTables::new();
-        symbol_tables.define("is_test", "boolean", &Kind::Field);
+        symbol_tables.define("is_test", "boolean", &Kind::Field, &Span::default());
         let actual = symbol_tables.index_of("is_test").unwrap();
         assert_eq!(0, actual)
     }
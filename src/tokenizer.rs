@@ -1,4 +1,6 @@
+pub mod dialect;
 pub mod jack_tokenizer;
 pub mod key_word;
-mod token;
+pub mod span;
+pub mod token;
 pub mod token_type;
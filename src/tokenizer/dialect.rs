@@ -0,0 +1,13 @@
+use clap::ValueEnum;
+
+/// The flavour of the Jack language accepted in sources.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Dialect {
+    /// The language of the nand2tetris book
+    #[default]
+    Standard,
+    /// Adds escape sequences in string constants, character constants like `'A'`,
+    /// hexadecimal and binary integer constants like `0x4000` and `0b1010`,
+    /// and the short-circuit operators `&&` and `||`
+    Extended,
+}
@@ -2,26 +2,55 @@ use std::collections::vec_deque::VecDeque;
 use std::fs;
 use std::path::Path;
 
-use anyhow::{bail, Context, Error, Result};
+use anyhow::{bail, Error, Result};
 
+use crate::error::diagnostic::Diagnostic;
+use crate::error::diagnostics::{Diagnostics, DEFAULT_ERROR_LIMIT};
+use crate::tokenizer::dialect::Dialect;
 use crate::tokenizer::key_word::{KeyWord, KEYWORDS};
+use crate::tokenizer::span::{LineIndex, Span};
 use crate::tokenizer::token::Token;
 use crate::tokenizer::token_type::TokenType;
 
 pub struct JackTokenizer {
     tokens: VecDeque<Token>,
     current_token: Token,
+    file_name: String,
+    diagnostics: Diagnostics,
 }
 
 impl JackTokenizer {
+    #[cfg(test)]
     pub fn new(path: &Path) -> Result<Self> {
+        Self::with_dialect(path, Dialect::Standard)
+    }
+
+    #[cfg(test)]
+    pub fn with_dialect(path: &Path, dialect: Dialect) -> Result<Self> {
+        Self::with_file_id(path, 0, dialect)
+    }
+
+    /// Tokenizes a source file. `file_id` tells the spans of the files compiled together apart.
+    pub fn with_file_id(path: &Path, file_id: usize, dialect: Dialect) -> Result<Self> {
         let code = fs::read_to_string(path)?;
-        let code_without_comments = Self::remove_comments(code)?;
-        let tokens = Self::tokenize(code_without_comments)?;
+        Self::from_source(&path.display().to_string(), &code, file_id, dialect)
+    }
+
+    /// Tokenizes source code that is not read from a file, such as the embedded OS declarations.
+    pub fn from_source(
+        file_name: &str,
+        code: &str,
+        file_id: usize,
+        dialect: Dialect,
+    ) -> Result<Self> {
+        let mut diagnostics = Diagnostics::new(DEFAULT_ERROR_LIMIT);
+        let tokens = Self::tokenize(code, file_name, file_id, dialect, &mut diagnostics)?;
 
         Ok(JackTokenizer {
             tokens,
             current_token: Default::default(),
+            file_name: String::from(file_name),
+            diagnostics,
         })
     }
 
@@ -30,28 +59,145 @@ impl JackTokenizer {
     }
 
     pub fn advance(&mut self) -> Result<()> {
-        if self.has_more_tokens()? {
-            self.current_token = self.tokens.pop_front().context("pop failed.")?
-        } else {
-            bail!(Error::msg("pop failed."))
+        match self.tokens.pop_front() {
+            Some(token) => self.current_token = token,
+            None => bail!(self.end_of_file()),
         }
         Ok(())
     }
 
     pub fn peek(&self) -> Result<&Token> {
-        if self.has_more_tokens()? {
-            self.tokens.get(0).context("get failed.")
-        } else {
-            bail!(Error::msg("get failed."))
+        match self.tokens.front() {
+            Some(token) => Ok(token),
+            None => bail!(self.end_of_file()),
         }
     }
 
     pub fn peek_second(&self) -> Result<&Token> {
-        if self.tokens.len() >= 2 {
-            self.tokens.get(1).context("get failed.")
-        } else {
-            bail!(Error::msg("get failed."))
+        match self.tokens.get(1) {
+            Some(token) => Ok(token),
+            None => bail!(self.end_of_file()),
+        }
+    }
+
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    pub fn span(&self) -> &Span {
+        self.current_token.span()
+    }
+
+    /// Consumes the next token if it is the given symbol.
+    pub fn expect_symbol(&mut self, symbol: char, context: &str) -> Result<()> {
+        let found = self.tokens.front().is_some_and(|token| {
+            token.token_type() == &TokenType::Symbol && token.value() == &symbol.to_string()
+        });
+        if !found {
+            bail!(self.unexpected(&format!("`{symbol}` {context}")))
+        }
+        self.advance()
+    }
+
+    /// Consumes the next token if it is the given keyword.
+    pub fn expect_keyword(&mut self, key_word: KeyWord, context: &str) -> Result<()> {
+        let found = self.tokens.front().is_some_and(|token| {
+            token.token_type() == &TokenType::Keyword
+                && KeyWord::from(token.value()).ok().as_ref() == Some(&key_word)
+        });
+        if !found {
+            let key_word = key_word.to_string().to_lowercase();
+            bail!(self.unexpected(&format!("`{key_word}` {context}")))
+        }
+        self.advance()
+    }
+
+    /// Consumes the next token if it is an identifier and returns it.
+    pub fn expect_identifier(&mut self, description: &str, context: &str) -> Result<String> {
+        let found = self
+            .tokens
+            .front()
+            .is_some_and(|token| token.token_type() == &TokenType::Identifier);
+        if !found {
+            bail!(self.unexpected(&format!("{description} {context}")))
         }
+        self.advance()?;
+        Ok(String::from(self.identifier()))
+    }
+
+    /// Consumes the next token if it is a type (’int’ | ’char’ | ’boolean’ | className) and returns it.
+    pub fn expect_type(&mut self, context: &str) -> Result<String> {
+        let found = self
+            .tokens
+            .front()
+            .is_some_and(|token| token.is_type().unwrap_or(false));
+        if !found {
+            bail!(self.unexpected(&format!("type {context}")))
+        }
+        self.advance()?;
+        Ok(String::from(self.current_token.value()))
+    }
+
+    /// Creates an error describing that the next token is not the expected one.
+    pub fn unexpected(&self, expected: &str) -> Error {
+        match self.tokens.front() {
+            Some(token) => self.error(&format!("expected {expected}, found {token}"), token.span()),
+            None => self.error(
+                &format!("expected {expected}, found end of file"),
+                self.current_token.span(),
+            ),
+        }
+    }
+
+    pub fn set_error_limit(&mut self, limit: usize) {
+        self.diagnostics.set_limit(limit);
+    }
+
+    /// Records a syntax error so that compilation can go on after it.
+    /// Other errors, and the error that reaches the limit, are returned to stop compilation.
+    pub fn recover(&mut self, error: Error) -> Result<()> {
+        let diagnostic = error.downcast::<Diagnostic>()?;
+        self.diagnostics.push(diagnostic);
+        if self.diagnostics.is_full() {
+            bail!(self.take_diagnostics())
+        }
+        Ok(())
+    }
+
+    /// Skips tokens up to the start of the next construct, stepping over nested blocks.
+    /// A ’;’ is consumed, while a ’}’ or one of the given keywords is left for the caller.
+    pub fn synchronize(&mut self, key_words: &[KeyWord]) {
+        let mut depth = 0;
+        while let Some(token) = self.tokens.front() {
+            match (token.token_type(), token.value().as_str()) {
+                (TokenType::Symbol, "{") => depth += 1,
+                (TokenType::Symbol, "}") if depth == 0 => return,
+                (TokenType::Symbol, "}") => depth -= 1,
+                (TokenType::Symbol, ";") if depth == 0 => {
+                    self.tokens.pop_front();
+                    return;
+                }
+                (TokenType::Keyword, value)
+                    if depth == 0 && KeyWord::from(value).is_ok_and(|k| key_words.contains(&k)) =>
+                {
+                    return
+                }
+                _ => {}
+            }
+            self.tokens.pop_front();
+        }
+    }
+
+    pub fn take_diagnostics(&mut self) -> Diagnostics {
+        self.diagnostics.take()
+    }
+
+    fn end_of_file(&self) -> Error {
+        self.error("unexpected end of file", self.current_token.span())
+    }
+
+    fn error(&self, message: &str, span: &Span) -> Error {
+        Error::new(Diagnostic::new(message, &self.file_name, span))
     }
 
     pub fn key_word(&self) -> Result<KeyWord> {
@@ -66,14 +212,26 @@ impl JackTokenizer {
         self.current_token.value()
     }
 
+    /// Hexadecimal and binary constants that don't fit in 16 bits are reported by the lexer and read as 0.
     pub fn int_val(&self) -> Result<usize> {
-        Ok(self.current_token.value().parse::<usize>()?)
+        Ok(Self::parse_int(self.current_token.value()).unwrap_or(0))
+    }
+
+    /// Returns true if the current integer constant is hexadecimal or binary,
+    /// which makes it a 16-bit pattern rather than a positive number.
+    pub fn is_bit_pattern(&self) -> bool {
+        matches!(self.current_token.value().get(..2), Some("0x" | "0b"))
     }
 
     pub fn string_val(&self) -> &String {
         self.current_token.value()
     }
 
+    /// Invalid character constants are reported by the lexer and read as 0.
+    pub fn char_val(&self) -> usize {
+        Self::char_code(self.current_token.value()).unwrap_or(0)
+    }
+
     pub fn is_term(&self) -> Result<bool> {
         match self.peek()?.token_type() {
             TokenType::Keyword => Ok(self.peek()?.is_keyword_constant()?),
@@ -81,102 +239,312 @@ impl JackTokenizer {
                 "(" | "-" | "~" => Ok(true),
                 _ => Ok(false),
             },
-            TokenType::Identifier | TokenType::IntConst | TokenType::StringConst => Ok(true),
+            TokenType::Identifier
+            | TokenType::IntConst
+            | TokenType::StringConst
+            | TokenType::CharConst => Ok(true),
         }
     }
 
-    fn remove_comments(code: String) -> Result<String> {
-        let mut code_without_comments = String::new();
-        let mut current_index = 0;
-
-        while current_index < code.len() {
-            let current_char = &code[current_index..current_index + 1];
+    /// Splits the source into tokens, skipping whitespace and comments.
+    /// Unterminated comments and string constants, as well as characters outside the Jack alphabet,
+    /// are reported as diagnostics rather than failing.
+    fn tokenize(
+        code: &str,
+        file_name: &str,
+        file_id: usize,
+        dialect: Dialect,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<VecDeque<Token>> {
+        let mut tokens: VecDeque<Token> = VecDeque::new();
 
-            match current_char {
-                "/" => {
-                    let next_char = &code[current_index + 1..current_index + 2];
-                    match next_char {
-                        "/" => {
-                            let end_index = code[current_index + 1..]
-                                .find('\n')
-                                .map(|i| i + current_index + 1)
-                                .context("map failed.")?;
-                            current_index = end_index + 1;
-                        }
-                        "*" => {
-                            let end_index = code[current_index + 1..]
-                                .find("*/")
-                                .map(|i| i + current_index + 1)
-                                .context("map failed.")?;
-                            current_index = end_index + 2;
+        let line_index = LineIndex::new(code);
+        let (offsets, chars): (Vec<usize>, Vec<char>) = code.char_indices().unzip();
+        let span = |start: usize, end: usize| {
+            let offset = offsets[start];
+            let end_offset = offsets.get(end).copied().unwrap_or(code.len());
+            line_index.span(file_id, offset, end_offset - offset)
+        };
+        let mut index = 0;
+        while index < chars.len() {
+            let current = chars[index];
+            match (current, chars.get(index + 1)) {
+                ('/', Some('/')) => {
+                    // a line comment ends at the end of the line or of the file
+                    while index < chars.len() && chars[index] != '\n' {
+                        index += 1;
+                    }
+                }
+                ('/', Some('*')) => {
+                    match (index + 2..chars.len()).find(|&i| chars[i..].starts_with(&['*', '/'])) {
+                        Some(end) => index = end + 2,
+                        None => {
+                            let diagnostic = Diagnostic::new(
+                                "unterminated block comment",
+                                file_name,
+                                &span(index, index + 2),
+                            )
+                            .with_help("close the comment with `*/`");
+                            diagnostics.push(diagnostic);
+                            index = chars.len();
                         }
-                        _ => {
-                            code_without_comments += &*String::from(current_char);
-                            current_index += 1;
+                    }
+                }
+                ('\"', _) => {
+                    // a string constant can't span lines
+                    let mut value = String::new();
+                    let mut end = index + 1;
+                    while end < chars.len() && !matches!(chars[end], '\"' | '\n') {
+                        match (chars[end], chars.get(end + 1)) {
+                            ('\\', Some(&escaped))
+                                if dialect == Dialect::Extended && escaped != '\n' =>
+                            {
+                                match Self::unescape(escaped) {
+                                    Some(c) => value.push(c),
+                                    None => {
+                                        let diagnostic = Diagnostic::new(
+                                            &format!("unknown escape sequence `\\{escaped}`"),
+                                            file_name,
+                                            &span(end, end + 2),
+                                        )
+                                        .with_help(
+                                            "the escape sequences are `\\\"`, `\\\\`, `\\n` and `\\t`",
+                                        );
+                                        diagnostics.push(diagnostic);
+                                    }
+                                }
+                                end += 2;
+                            }
+                            (c, _) => {
+                                if !Self::is_hack_character(c) {
+                                    diagnostics.push(Self::non_hack_character(
+                                        c,
+                                        file_name,
+                                        &span(end, end + 1),
+                                    ));
+                                }
+                                value.push(c);
+                                end += 1;
+                            }
                         }
                     }
+                    let token = Token::new(TokenType::StringConst, value);
+                    if chars.get(end) == Some(&'\"') {
+                        tokens.push_back(token.with_span(span(index, end + 1)));
+                        index = end + 1;
+                    } else {
+                        let diagnostic = Diagnostic::new(
+                            "unterminated string constant",
+                            file_name,
+                            &span(index, end),
+                        )
+                        .with_help("close the string with `\"` on the same line");
+                        diagnostics.push(diagnostic);
+                        // keep the token so that parsing goes on without more errors
+                        tokens.push_back(token.with_span(span(index, end)));
+                        index = end;
+                    }
                 }
-                "\r" | "\n" => current_index += 1,
-                _ => {
-                    code_without_comments += &*String::from(current_char);
-                    current_index += 1;
+                ('\'', _) if dialect == Dialect::Extended => {
+                    // the quote of `'\''` doesn't close the constant
+                    let start = index + 1;
+                    let first = match chars.get(start) {
+                        Some('\\') if chars.get(start + 1).is_some_and(|&c| c != '\n') => start + 2,
+                        _ => start,
+                    };
+                    let end = (first..chars.len())
+                        .find(|&i| matches!(chars[i], '\'' | '\n'))
+                        .unwrap_or(chars.len());
+                    let spelling: String = chars[start..end].iter().collect();
+                    let token = Token::new(TokenType::CharConst, spelling);
+                    if chars.get(end) == Some(&'\'') {
+                        if let [c] = chars[start..end] {
+                            if !Self::is_hack_character(c) {
+                                diagnostics.push(Self::non_hack_character(
+                                    c,
+                                    file_name,
+                                    &span(start, end),
+                                ));
+                            }
+                        } else if Self::char_code(token.value()).is_none() {
+                            let diagnostic = Diagnostic::new(
+                                &format!("invalid character constant {token}"),
+                                file_name,
+                                &span(index, end + 1),
+                            )
+                            .with_help(
+                                "a character constant holds one ASCII character or an escape like `\\n` or `\\left`",
+                            );
+                            diagnostics.push(diagnostic);
+                        }
+                        tokens.push_back(token.with_span(span(index, end + 1)));
+                        index = end + 1;
+                    } else {
+                        let diagnostic = Diagnostic::new(
+                            "unterminated character constant",
+                            file_name,
+                            &span(index, end),
+                        )
+                        .with_help("close the character with `'` on the same line");
+                        diagnostics.push(diagnostic);
+                        tokens.push_back(token.with_span(span(index, end)));
+                        index = end;
+                    }
                 }
-            }
-        }
-        Ok(code_without_comments)
-    }
-
-    fn tokenize(source: String) -> Result<VecDeque<Token>> {
-        let mut tokens: VecDeque<Token> = VecDeque::new();
-
-        let mut index = 0;
-        let chars: Vec<char> = source.chars().collect();
-        while index < chars.len() {
-            let current = chars[index];
-            match current {
-                '\"' => {
-                    index += 1;
-                    let (token, index_after_tokenize) = Self::tokenize_string_const(index, &chars)?;
-                    tokens.push_back(token);
-                    index = index_after_tokenize;
+                _ if dialect == Dialect::Extended
+                    && EXTENDED_SYMBOLS
+                        .iter()
+                        .any(|symbol| chars[index..].starts_with(symbol)) =>
+                {
+                    let token =
+                        Token::new(TokenType::Symbol, chars[index..index + 2].iter().collect());
+                    tokens.push_back(token.with_span(span(index, index + 2)));
+                    index += 2;
                 }
-                current if SYMBOLS.contains(&current) => {
-                    tokens.push_back(Token::new(TokenType::Symbol, String::from(current)));
+                (current, _) if SYMBOLS.contains(&current) => {
+                    let token = Token::new(TokenType::Symbol, String::from(current));
+                    tokens.push_back(token.with_span(span(index, index + 1)));
                     index += 1;
                 }
-                current if current.is_alphabetic() => {
+                (current, _) if current.is_ascii_alphabetic() || current == '_' => {
                     let (token, index_after_tokenize) =
                         Self::tokenize_keyword_and_identifier(index, &chars)?;
-                    tokens.push_back(token);
+                    tokens.push_back(token.with_span(span(index, index_after_tokenize)));
                     index = index_after_tokenize;
                 }
-                current if current.is_numeric() => {
-                    let (token, index_after_tokenize) = Self::tokenize_int_const(index, &chars)?;
-                    tokens.push_back(token);
+                (current, _) if current.is_ascii_digit() => {
+                    let (mut token, mut index_after_tokenize) =
+                        Self::tokenize_int_const(index, &chars, dialect)?;
+                    let in_word = chars
+                        .get(index_after_tokenize)
+                        .is_some_and(|c| c.is_ascii_alphanumeric() || *c == '_');
+                    let spelling = token.value();
+                    let message = if in_word {
+                        // kept as one identifier, so the parser doesn't trip over its pieces
+                        (token, index_after_tokenize) =
+                            Self::tokenize_keyword_and_identifier(index, &chars)?;
+                        Some(String::from("identifier can't start with a digit"))
+                    } else if Self::parse_int(spelling).is_none() && spelling.len() == 2 {
+                        Some(format!("missing digits after `{spelling}`"))
+                    } else if Self::parse_int(spelling).is_none() {
+                        Some(format!(
+                            "integer constant `{spelling}` doesn't fit in 16 bits"
+                        ))
+                    } else {
+                        None
+                    };
+                    if let Some(message) = message {
+                        let diagnostic = Diagnostic::new(
+                            &message,
+                            file_name,
+                            &span(index, index_after_tokenize),
+                        );
+                        diagnostics.push(diagnostic);
+                    }
+                    tokens.push_back(token.with_span(span(index, index_after_tokenize)));
                     index = index_after_tokenize;
                 }
-                _ => index += 1,
+                (current, _) if current.is_whitespace() => index += 1,
+                (current, _) => {
+                    let mut diagnostic = Diagnostic::new(
+                        &format!("unexpected character `{current}`"),
+                        file_name,
+                        &span(index, index + 1),
+                    );
+                    if current == '\'' {
+                        diagnostic =
+                            diagnostic.with_help("character constants need `--dialect extended`");
+                    }
+                    diagnostics.push(diagnostic);
+                    index += 1;
+                }
             }
         }
         Ok(tokens)
     }
 
-    fn tokenize_string_const(mut index: usize, chars: &Vec<char>) -> Result<(Token, usize)> {
-        let mut value = String::new();
-        while index < chars.len() && chars[index] != '\"' {
-            value.push(chars[index]);
-            index += 1;
+    /// Returns whether a character can be written as it is in a string or character constant.
+    /// The Hack character set has the printable ASCII characters, and its other characters need escapes.
+    fn is_hack_character(c: char) -> bool {
+        c == ' ' || c.is_ascii_graphic()
+    }
+
+    fn non_hack_character(c: char, file_name: &str, span: &Span) -> Diagnostic {
+        Diagnostic::new(
+            &format!("character `{c}` is not in the Hack character set"),
+            file_name,
+            span,
+        )
+        .with_help("strings and character constants hold printable ASCII characters")
+    }
+
+    /// Returns the character written as `\c` in a string or character constant of the extended dialect.
+    fn unescape(c: char) -> Option<char> {
+        match c {
+            '"' => Some('"'),
+            '\\' => Some('\\'),
+            // the Hack character set has its own newline
+            'n' => char::from_u32(128),
+            't' => Some('\t'),
+            _ => None,
         }
-        index += 1;
-        Ok((Token::new(TokenType::StringConst, value), index))
     }
 
-    fn tokenize_keyword_and_identifier(
-        mut index: usize,
-        chars: &Vec<char>,
-    ) -> Result<(Token, usize)> {
+    /// Returns the code of a character constant from what is written between its quotes, like `A` or `\left`.
+    /// Besides the escapes of strings, the special keys of the Hack character set have named escapes.
+    fn char_code(spelling: &str) -> Option<usize> {
+        let mut chars = spelling.chars();
+        match (chars.next()?, chars.as_str()) {
+            ('\\', name) => {
+                let code = match name {
+                    "'" => 39,
+                    "b" | "backspace" => 129,
+                    "left" => 130,
+                    "up" => 131,
+                    "right" => 132,
+                    "down" => 133,
+                    "home" => 134,
+                    "end" => 135,
+                    "pageup" => 136,
+                    "pagedown" => 137,
+                    "insert" => 138,
+                    "delete" => 139,
+                    "esc" => 140,
+                    _ => match name.strip_prefix('f').and_then(|n| n.parse::<usize>().ok()) {
+                        Some(n @ 1..=12) => 140 + n,
+                        _ => {
+                            let mut escaped = name.chars();
+                            match (escaped.next(), escaped.next()) {
+                                (Some(c), None) => Self::unescape(c)? as usize,
+                                _ => return None,
+                            }
+                        }
+                    },
+                };
+                Some(code)
+            }
+            (c, "") if Self::is_hack_character(c) => Some(c as usize),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of an integer constant, or None if it is hexadecimal or binary and doesn't fit in 16 bits.
+    /// Decimal constants too large for a usize saturate, the checker reports everything above 32767 anyway.
+    fn parse_int(spelling: &str) -> Option<usize> {
+        let (digits, radix) = match spelling.get(..2) {
+            Some("0x") => (&spelling[2..], 16),
+            Some("0b") => (&spelling[2..], 2),
+            _ => return Some(spelling.parse().unwrap_or(usize::MAX)),
+        };
+        usize::from_str_radix(digits, radix)
+            .ok()
+            .filter(|&value| value <= u16::MAX as usize)
+    }
+
+    fn tokenize_keyword_and_identifier(mut index: usize, chars: &[char]) -> Result<(Token, usize)> {
         let mut value = String::new();
-        while index < chars.len() && chars[index].is_alphabetic() {
+        // an identifier is made of letters, digits and `_`, but doesn't start with a digit
+        while index < chars.len() && (chars[index].is_ascii_alphanumeric() || chars[index] == '_') {
             value.push(chars[index]);
             index += 1;
         }
@@ -187,9 +555,22 @@ impl JackTokenizer {
         }
     }
 
-    fn tokenize_int_const(mut index: usize, chars: &Vec<char>) -> Result<(Token, usize)> {
+    fn tokenize_int_const(
+        mut index: usize,
+        chars: &[char],
+        dialect: Dialect,
+    ) -> Result<(Token, usize)> {
         let mut value = String::new();
-        while index < chars.len() && chars[index].is_numeric() {
+        let radix = match (chars[index], chars.get(index + 1)) {
+            ('0', Some('x')) if dialect == Dialect::Extended => 16,
+            ('0', Some('b')) if dialect == Dialect::Extended => 2,
+            _ => 10,
+        };
+        if radix != 10 {
+            value.extend(&chars[index..index + 2]);
+            index += 2;
+        }
+        while index < chars.len() && chars[index].is_digit(radix) {
             value.push(chars[index]);
             index += 1;
         }
@@ -201,11 +582,19 @@ const SYMBOLS: [char; 19] = [
     '{', '}', '(', ')', '[', ']', '.', ',', ';', '+', '-', '*', '/', '&', '|', '<', '>', '=', '~',
 ];
 
+/// The short-circuit operators, which are read as two `&` or `|` in the standard dialect
+const EXTENDED_SYMBOLS: [[char; 2]; 2] = [['&', '&'], ['|', '|']];
+
 #[cfg(test)]
 mod tests {
     use std::collections::VecDeque;
+    use std::io::{Seek, Write};
 
+    use crate::error::diagnostics::{Diagnostics, DEFAULT_ERROR_LIMIT};
+    use crate::tokenizer::dialect::Dialect;
     use crate::tokenizer::jack_tokenizer::JackTokenizer;
+    use crate::tokenizer::key_word::KeyWord;
+    use crate::tokenizer::span::Span;
     use crate::tokenizer::token::Token;
     use crate::tokenizer::token_type::TokenType;
 
@@ -219,8 +608,409 @@ mod tests {
         let tokenizer = JackTokenizer {
             tokens,
             current_token: Default::default(),
+            file_name: String::from("Main.jack"),
+            diagnostics: Diagnostics::new(DEFAULT_ERROR_LIMIT),
         };
 
         assert!(tokenizer.is_term().unwrap())
     }
+
+    #[test]
+    fn can_track_spans_of_tokens() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "/** Main */ class Main {{").unwrap();
+        writeln!(src_file, "  // comment").unwrap();
+        writeln!(src_file, "  let s = \"ab\";").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::with_file_id(path, 3, Dialect::Standard).unwrap();
+
+        assert_eq!(
+            &Span::new(3, 12, 1, 13, 5),
+            tokenizer.peek().unwrap().span()
+        );
+        tokenizer.advance().unwrap();
+        assert_eq!(&Span::new(3, 12, 1, 13, 5), tokenizer.span());
+        tokenizer.advance().unwrap();
+        tokenizer.advance().unwrap();
+        tokenizer.advance().unwrap();
+        assert_eq!(&Span::new(3, 40, 3, 3, 3), tokenizer.span());
+        tokenizer.advance().unwrap();
+        tokenizer.advance().unwrap();
+        tokenizer.advance().unwrap();
+        assert_eq!("ab", tokenizer.string_val());
+        assert_eq!(&Span::new(3, 48, 3, 11, 4), tokenizer.span());
+    }
+
+    #[test]
+    fn can_tell_spans_of_files_apart() {
+        let mut main_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(main_file, "class Main {{}}").unwrap();
+        main_file.rewind().unwrap();
+        let mut point_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(point_file, "class Point {{}}").unwrap();
+        point_file.rewind().unwrap();
+
+        let main = JackTokenizer::with_file_id(main_file.path(), 0, Dialect::Standard).unwrap();
+        let point = JackTokenizer::with_file_id(point_file.path(), 1, Dialect::Standard).unwrap();
+
+        assert_eq!(&Span::new(0, 0, 1, 1, 5), main.peek().unwrap().span());
+        assert_eq!(&Span::new(1, 0, 1, 1, 5), point.peek().unwrap().span());
+    }
+
+    #[test]
+    fn can_tokenize_comment_markers_in_strings() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "/* café */ let s = \"http://x /* y */\";").unwrap();
+        write!(src_file, "do f(); // ☕ without a line break").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let mut actual = Vec::new();
+        let mut spans = Vec::new();
+        while tokenizer.has_more_tokens().unwrap() {
+            tokenizer.advance().unwrap();
+            actual.push(tokenizer.identifier().clone());
+            spans.push(*tokenizer.span());
+        }
+
+        assert_eq!(
+            vec![
+                "let",
+                "s",
+                "=",
+                "http://x /* y */",
+                ";",
+                "do",
+                "f",
+                "(",
+                ")",
+                ";"
+            ],
+            actual
+        );
+        // `é` takes two bytes but one column
+        assert_eq!(Span::new(0, 20, 1, 20, 18), spans[3]);
+        assert!(tokenizer.take_diagnostics().is_empty());
+    }
+
+    #[test]
+    fn can_tokenize_identifiers_with_digits_and_underscores() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "let player_1 = _tmp2 + 3x;").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let mut actual = Vec::new();
+        while tokenizer.has_more_tokens().unwrap() {
+            let token = tokenizer.peek().unwrap();
+            actual.push(format!("{:?} {}", token.token_type(), token.value()));
+            tokenizer.advance().unwrap();
+        }
+        let diagnostics: Vec<String> = tokenizer
+            .take_diagnostics()
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+
+        assert_eq!(
+            vec![
+                "Keyword let",
+                "Identifier player_1",
+                "Symbol =",
+                "Identifier _tmp2",
+                "Symbol +",
+                "Identifier 3x",
+                "Symbol ;",
+            ],
+            actual
+        );
+        assert_eq!(
+            vec![format!(
+                "identifier can't start with a digit at {}:1:24",
+                path.display()
+            )],
+            diagnostics
+        );
+    }
+
+    #[test]
+    fn can_report_unexpected_characters() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "let café = a @ b;").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let actual: Vec<String> = tokenizer
+            .take_diagnostics()
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+
+        assert_eq!(
+            vec![
+                format!("unexpected character `é` at {}:1:8", path.display()),
+                format!("unexpected character `@` at {}:1:14", path.display()),
+            ],
+            actual
+        );
+    }
+
+    #[test]
+    fn can_unescape_strings_in_extended_dialect() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, r#""say \"hi\"\n\t\\" "\q""#).unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut standard = JackTokenizer::with_dialect(path, Dialect::Standard).unwrap();
+        standard.advance().unwrap();
+        assert_eq!(r"say \", standard.string_val());
+
+        let mut extended = JackTokenizer::with_dialect(path, Dialect::Extended).unwrap();
+        extended.advance().unwrap();
+        assert_eq!("say \"hi\"\u{80}\t\\", extended.string_val());
+        extended.advance().unwrap();
+        assert_eq!("", extended.string_val());
+        let actual: Vec<String> = extended
+            .take_diagnostics()
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+        assert_eq!(
+            vec![format!(
+                "unknown escape sequence `\\q` at {}:1:21\n  = help: the escape sequences are `\\\"`, `\\\\`, `\\n` and `\\t`",
+                path.display()
+            )],
+            actual
+        );
+    }
+
+    #[test]
+    fn can_tokenize_char_constants_in_extended_dialect() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, r"'A' '\n' '\left' '\f12' '\'' 'ab' '\x' ''").unwrap();
+        writeln!(src_file, "'z").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::with_dialect(path, Dialect::Extended).unwrap();
+        let mut actual = Vec::new();
+        while tokenizer.has_more_tokens().unwrap() {
+            tokenizer.advance().unwrap();
+            actual.push(tokenizer.char_val());
+        }
+        let diagnostics: Vec<String> = tokenizer
+            .take_diagnostics()
+            .iter()
+            .map(|d| d.message.clone())
+            .collect();
+
+        assert_eq!(vec![65, 128, 130, 152, 39, 0, 0, 0, 122], actual);
+        assert_eq!(
+            vec![
+                "invalid character constant `'ab'`",
+                "invalid character constant `'\\x'`",
+                "invalid character constant `''`",
+                "unterminated character constant",
+            ],
+            diagnostics
+        );
+    }
+
+    #[test]
+    fn can_report_characters_outside_hack_character_set() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "\"a😀b\" '😀' 'é'").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::with_dialect(path, Dialect::Extended).unwrap();
+        let actual: Vec<String> = tokenizer
+            .take_diagnostics()
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+
+        let expected: Vec<String> = [("😀", 3), ("😀", 8), ("é", 12)]
+            .iter()
+            .map(|(c, column)| {
+                format!(
+                    "character `{c}` is not in the Hack character set at {}:1:{column}\n  = help: strings and character constants hold printable ASCII characters",
+                    path.display()
+                )
+            })
+            .collect();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_report_bit_patterns_out_of_range() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "0x10000 0b 0b11 0x1f").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::with_dialect(path, Dialect::Extended).unwrap();
+        let mut actual = Vec::new();
+        while tokenizer.has_more_tokens().unwrap() {
+            tokenizer.advance().unwrap();
+            actual.push(tokenizer.int_val().unwrap());
+        }
+        let diagnostics: Vec<String> = tokenizer
+            .take_diagnostics()
+            .iter()
+            .map(|d| d.message.clone())
+            .collect();
+
+        assert_eq!(vec![0, 0, 3, 31], actual);
+        assert_eq!(
+            vec![
+                "integer constant `0x10000` doesn't fit in 16 bits",
+                "missing digits after `0b`",
+            ],
+            diagnostics
+        );
+    }
+
+    #[test]
+    fn can_tokenize_short_circuit_operators_in_extended_dialect() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "a && b ||| c & d").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let values = |dialect| {
+            let mut tokenizer = JackTokenizer::with_dialect(path, dialect).unwrap();
+            let mut values = Vec::new();
+            while tokenizer.has_more_tokens().unwrap() {
+                values.push(tokenizer.peek().unwrap().value().clone());
+                tokenizer.advance().unwrap();
+            }
+            values
+        };
+
+        assert_eq!(
+            vec!["a", "&", "&", "b", "|", "|", "|", "c", "&", "d"],
+            values(Dialect::Standard)
+        );
+        assert_eq!(
+            vec!["a", "&&", "b", "||", "|", "c", "&", "d"],
+            values(Dialect::Extended)
+        );
+    }
+
+    #[test]
+    fn can_report_unterminated_string_and_comment() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "let s = \"abc;").unwrap();
+        writeln!(src_file, "let t = 1; /* never closed").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let actual: Vec<String> = tokenizer
+            .take_diagnostics()
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+
+        assert_eq!(
+            vec![
+                format!(
+                    "unterminated string constant at {}:1:9\n  = help: close the string with `\"` on the same line",
+                    path.display()
+                ),
+                format!(
+                    "unterminated block comment at {}:2:12\n  = help: close the comment with `*/`",
+                    path.display()
+                ),
+            ],
+            actual
+        );
+        assert_eq!(9, tokenizer.tokens.len());
+    }
+
+    #[test]
+    fn can_report_unexpected_token() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class {{").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        tokenizer.advance().unwrap();
+        let actual = tokenizer
+            .expect_identifier("class name", "after `class`")
+            .unwrap_err()
+            .to_string();
+
+        let expected = format!(
+            "expected class name after `class`, found `{{` at {}:1:7",
+            path.display()
+        );
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_report_end_of_file() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "return").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        tokenizer.advance().unwrap();
+        let actual = tokenizer
+            .expect_symbol(';', "after return statement")
+            .unwrap_err()
+            .to_string();
+
+        let expected = format!(
+            "expected `;` after return statement, found end of file at {}:1:1",
+            path.display()
+        );
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_synchronize_over_blocks() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "= x) {{ let a = 1; }} else {{ }} y; while").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        tokenizer.synchronize(&[KeyWord::Let, KeyWord::While]);
+
+        assert_eq!("while", tokenizer.peek().unwrap().value());
+    }
+
+    #[test]
+    fn can_stop_recovering_at_limit() {
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "1 2").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        tokenizer.set_error_limit(2);
+
+        let first = tokenizer
+            .expect_symbol(';', "after expression")
+            .unwrap_err();
+        assert!(tokenizer.recover(first).is_ok());
+        tokenizer.advance().unwrap();
+        let second = tokenizer
+            .expect_symbol(';', "after expression")
+            .unwrap_err();
+        let actual = tokenizer.recover(second).unwrap_err();
+
+        assert_eq!(2, actual.downcast::<Diagnostics>().unwrap().len());
+        assert!(tokenizer.take_diagnostics().is_empty());
+    }
 }
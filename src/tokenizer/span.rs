@@ -0,0 +1,64 @@
+/// Location of a token in the original source text.
+/// `offset` and `length` are in bytes, `line` and `column` start at 1.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub file_id: usize,
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+}
+
+impl Span {
+    pub fn new(file_id: usize, offset: usize, line: usize, column: usize, length: usize) -> Self {
+        Span {
+            file_id,
+            offset,
+            line,
+            column,
+            length,
+        }
+    }
+
+    /// Returns the span from the start of this span to the end of the given one.
+    pub fn to(&self, end: &Span) -> Span {
+        Span {
+            length: end.offset + end.length - self.offset,
+            ..*self
+        }
+    }
+}
+
+/// Maps byte offsets of a source text to lines and columns.
+pub struct LineIndex<'a> {
+    code: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(code: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(code.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        LineIndex { code, line_starts }
+    }
+
+    pub fn span(&self, file_id: usize, offset: usize, length: usize) -> Span {
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let line_start = self.line_starts[line - 1];
+        let column = self.code[line_start..offset].chars().count() + 1;
+        Span::new(file_id, offset, line, column, length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::span::{LineIndex, Span};
+
+    #[test]
+    fn can_create_span_from_offset() {
+        let line_index = LineIndex::new("class Main {\r\n    field int x;\n}");
+        let actual = line_index.span(0, 24, 3);
+        assert_eq!(Span::new(0, 24, 2, 11, 3), actual);
+    }
+}
@@ -1,14 +1,17 @@
-use std::fmt::Debug;
+use std::fmt;
+use std::fmt::{Debug, Display, Formatter};
 
 use anyhow::Result;
 
 use crate::tokenizer::key_word::KeyWord;
+use crate::tokenizer::span::Span;
 use crate::tokenizer::token_type::TokenType;
 
 #[derive(Debug)]
 pub struct Token {
     token_type: TokenType,
     value: String,
+    span: Span,
 }
 
 impl Default for Token {
@@ -16,13 +19,22 @@ impl Default for Token {
         Self {
             token_type: TokenType::Keyword,
             value: "".to_string(),
+            span: Default::default(),
         }
     }
 }
 
 impl Token {
     pub fn new(token_type: TokenType, value: String) -> Self {
-        Token { token_type, value }
+        Token {
+            token_type,
+            value,
+            span: Default::default(),
+        }
+    }
+
+    pub fn with_span(self, span: Span) -> Self {
+        Token { span, ..self }
     }
 
     pub fn token_type(&self) -> &TokenType {
@@ -33,13 +45,20 @@ impl Token {
         &self.value
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
     pub fn is_type(&self) -> Result<bool> {
         match self.token_type {
             TokenType::Keyword => match KeyWord::from(&self.value)? {
                 KeyWord::Int | KeyWord::Char | KeyWord::Boolean => Ok(true),
                 _ => Ok(false),
             },
-            TokenType::Symbol | TokenType::IntConst | TokenType::StringConst => Ok(false),
+            TokenType::Symbol
+            | TokenType::IntConst
+            | TokenType::StringConst
+            | TokenType::CharConst => Ok(false),
             TokenType::Identifier => Ok(true),
         }
     }
@@ -47,7 +66,7 @@ impl Token {
     pub fn is_op(&self) -> bool {
         matches!(
             self.value.as_str(),
-            "+" | "-" | "*" | "/" | "&" | "|" | "<" | ">" | "="
+            "+" | "-" | "*" | "/" | "&" | "|" | "<" | ">" | "=" | "&&" | "||"
         )
     }
 
@@ -61,3 +80,13 @@ impl Token {
         }
     }
 }
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.token_type {
+            TokenType::StringConst => write!(f, "`\"{}\"`", self.value),
+            TokenType::CharConst => write!(f, "`'{}'`", self.value),
+            _ => write!(f, "`{}`", self.value),
+        }
+    }
+}
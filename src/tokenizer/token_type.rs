@@ -5,4 +5,5 @@ pub enum TokenType {
     Identifier,
     IntConst,
     StringConst,
+    CharConst,
 }
@@ -1,4 +1,7 @@
 pub mod command;
 pub mod label;
+pub mod parse_tree_writer;
 pub mod segment;
+pub mod vm_command;
 pub mod vm_writer;
+pub mod xml_writer;
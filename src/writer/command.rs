@@ -1,7 +1,7 @@
 use std::fmt::{Display, Formatter};
 
 #[allow(dead_code)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Command {
     Add,
     Sub,
@@ -15,16 +15,16 @@ pub enum Command {
 }
 
 impl Command {
-    pub fn from(value: char) -> Option<Self> {
+    pub fn from(value: &str) -> Option<Self> {
         match value {
-            '+' => Some(Command::Add),
-            '-' => Some(Command::Sub),
-            '=' => Some(Command::Eq),
-            '>' => Some(Command::Gt),
-            '<' => Some(Command::Lt),
-            '&' => Some(Command::And),
-            '|' => Some(Command::Or),
-            '~' => Some(Command::Not),
+            "+" => Some(Command::Add),
+            "-" => Some(Command::Sub),
+            "=" => Some(Command::Eq),
+            ">" => Some(Command::Gt),
+            "<" => Some(Command::Lt),
+            "&" => Some(Command::And),
+            "|" => Some(Command::Or),
+            "~" => Some(Command::Not),
             _ => None,
         }
     }
@@ -1,22 +1,39 @@
+use std::collections::HashMap;
+
 use mockall::automock;
-use rand::distributions::Alphanumeric;
-use rand::{thread_rng, Rng};
 
 #[automock]
 pub trait LabelCreator {
-    fn create(&self, command: &str) -> String;
+    /// Returns a number not yet used by the labels of the given kind of statement, such as `IF` or `WHILE`.
+    fn next_index(&mut self, statement: &str) -> usize;
 }
 
+/// Numbers the labels of each kind of statement from 0, like `IF_TRUE0` and `WHILE_EXP0`.
+/// A new one is used for every subroutine, so the output only changes where the source does.
 #[derive(Default)]
-pub struct RandomLabelCreator {}
+pub struct CounterLabelCreator {
+    counters: HashMap<String, usize>,
+}
+
+impl LabelCreator for CounterLabelCreator {
+    fn next_index(&mut self, statement: &str) -> usize {
+        let counter = self.counters.entry(String::from(statement)).or_default();
+        let index = *counter;
+        *counter += 1;
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::writer::label::{CounterLabelCreator, LabelCreator};
 
-impl LabelCreator for RandomLabelCreator {
-    fn create(&self, command: &str) -> String {
-        let random_label: String = thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(8)
-            .map(char::from)
-            .collect();
-        format!("{command}_{random_label}")
+    #[test]
+    fn can_count_per_statement() {
+        let mut label_creator = CounterLabelCreator::default();
+        assert_eq!(0, label_creator.next_index("IF"));
+        assert_eq!(1, label_creator.next_index("IF"));
+        assert_eq!(0, label_creator.next_index("WHILE"));
+        assert_eq!(2, label_creator.next_index("IF"));
     }
 }
@@ -0,0 +1,451 @@
+use std::io::Write;
+
+use anyhow::Result;
+
+use crate::ast::class::{Class, ClassVarDec, Parameter, SubroutineBody, SubroutineDec, VarDec};
+use crate::ast::expression::{Expression, KeywordConstant, SubroutineCall, Term};
+use crate::ast::identifier::Identifier;
+use crate::ast::statement::Statement;
+use crate::ast::type_name::TypeName;
+use crate::writer::xml_writer::XmlWriter;
+
+/// Writes the parse tree of a class in the XML format of the nand2tetris project 10 compare files.
+/// Every grammar rule except `statement`, `subroutineCall` and the lexical ones becomes an element.
+pub struct ParseTreeWriter {}
+
+impl ParseTreeWriter {
+    pub fn write_class(class: &Class, written: &mut impl Write) -> Result<()> {
+        XmlWriter::write_open("class", 0, written)?;
+        Self::write_keyword("class", 1, written)?;
+        Self::write_identifier(&class.name, 1, written)?;
+        Self::write_symbol('{', 1, written)?;
+        for class_var_dec in &class.class_var_decs {
+            Self::write_class_var_dec(class_var_dec, 1, written)?;
+        }
+        for subroutine_dec in &class.subroutine_decs {
+            Self::write_subroutine_dec(subroutine_dec, 1, written)?;
+        }
+        Self::write_symbol('}', 1, written)?;
+        XmlWriter::write_close("class", 0, written)
+    }
+
+    fn write_class_var_dec(
+        class_var_dec: &ClassVarDec,
+        depth: usize,
+        written: &mut impl Write,
+    ) -> Result<()> {
+        XmlWriter::write_open("classVarDec", depth, written)?;
+        Self::write_keyword(&class_var_dec.kind.to_string(), depth + 1, written)?;
+        Self::write_type(&class_var_dec.type_name, depth + 1, written)?;
+        Self::write_var_names(&class_var_dec.var_names, depth + 1, written)?;
+        Self::write_symbol(';', depth + 1, written)?;
+        XmlWriter::write_close("classVarDec", depth, written)
+    }
+
+    fn write_subroutine_dec(
+        subroutine_dec: &SubroutineDec,
+        depth: usize,
+        written: &mut impl Write,
+    ) -> Result<()> {
+        XmlWriter::write_open("subroutineDec", depth, written)?;
+        Self::write_keyword(&subroutine_dec.kind.to_string(), depth + 1, written)?;
+        match &subroutine_dec.return_type {
+            Some(type_name) => Self::write_type(type_name, depth + 1, written)?,
+            None => Self::write_keyword("void", depth + 1, written)?,
+        }
+        Self::write_identifier(&subroutine_dec.name, depth + 1, written)?;
+        Self::write_symbol('(', depth + 1, written)?;
+        Self::write_parameter_list(&subroutine_dec.parameters, depth + 1, written)?;
+        Self::write_symbol(')', depth + 1, written)?;
+        Self::write_subroutine_body(&subroutine_dec.body, depth + 1, written)?;
+        XmlWriter::write_close("subroutineDec", depth, written)
+    }
+
+    fn write_parameter_list(
+        parameters: &[Parameter],
+        depth: usize,
+        written: &mut impl Write,
+    ) -> Result<()> {
+        XmlWriter::write_open("parameterList", depth, written)?;
+        for (i, parameter) in parameters.iter().enumerate() {
+            if i > 0 {
+                Self::write_symbol(',', depth + 1, written)?;
+            }
+            Self::write_type(&parameter.type_name, depth + 1, written)?;
+            Self::write_identifier(&parameter.var_name, depth + 1, written)?;
+        }
+        XmlWriter::write_close("parameterList", depth, written)
+    }
+
+    fn write_subroutine_body(
+        subroutine_body: &SubroutineBody,
+        depth: usize,
+        written: &mut impl Write,
+    ) -> Result<()> {
+        XmlWriter::write_open("subroutineBody", depth, written)?;
+        Self::write_symbol('{', depth + 1, written)?;
+        for var_dec in &subroutine_body.var_decs {
+            Self::write_var_dec(var_dec, depth + 1, written)?;
+        }
+        Self::write_statements(&subroutine_body.statements, depth + 1, written)?;
+        Self::write_symbol('}', depth + 1, written)?;
+        XmlWriter::write_close("subroutineBody", depth, written)
+    }
+
+    fn write_var_dec(var_dec: &VarDec, depth: usize, written: &mut impl Write) -> Result<()> {
+        XmlWriter::write_open("varDec", depth, written)?;
+        Self::write_keyword("var", depth + 1, written)?;
+        Self::write_type(&var_dec.type_name, depth + 1, written)?;
+        Self::write_var_names(&var_dec.var_names, depth + 1, written)?;
+        Self::write_symbol(';', depth + 1, written)?;
+        XmlWriter::write_close("varDec", depth, written)
+    }
+
+    fn write_statements(
+        statements: &[Statement],
+        depth: usize,
+        written: &mut impl Write,
+    ) -> Result<()> {
+        XmlWriter::write_open("statements", depth, written)?;
+        for statement in statements {
+            Self::write_statement(statement, depth + 1, written)?;
+        }
+        XmlWriter::write_close("statements", depth, written)
+    }
+
+    fn write_statement(
+        statement: &Statement,
+        depth: usize,
+        written: &mut impl Write,
+    ) -> Result<()> {
+        match statement {
+            Statement::Let(statement) => {
+                XmlWriter::write_open("letStatement", depth, written)?;
+                Self::write_keyword("let", depth + 1, written)?;
+                Self::write_identifier(&statement.var_name, depth + 1, written)?;
+                if let Some(index) = &statement.index {
+                    Self::write_symbol('[', depth + 1, written)?;
+                    Self::write_expression(index, depth + 1, written)?;
+                    Self::write_symbol(']', depth + 1, written)?;
+                }
+                Self::write_symbol('=', depth + 1, written)?;
+                Self::write_expression(&statement.value, depth + 1, written)?;
+                Self::write_symbol(';', depth + 1, written)?;
+                XmlWriter::write_close("letStatement", depth, written)
+            }
+            Statement::If(statement) => {
+                XmlWriter::write_open("ifStatement", depth, written)?;
+                Self::write_keyword("if", depth + 1, written)?;
+                Self::write_symbol('(', depth + 1, written)?;
+                Self::write_expression(&statement.condition, depth + 1, written)?;
+                Self::write_symbol(')', depth + 1, written)?;
+                Self::write_symbol('{', depth + 1, written)?;
+                Self::write_statements(&statement.statements, depth + 1, written)?;
+                Self::write_symbol('}', depth + 1, written)?;
+                if let Some(else_statements) = &statement.else_statements {
+                    Self::write_keyword("else", depth + 1, written)?;
+                    Self::write_symbol('{', depth + 1, written)?;
+                    Self::write_statements(else_statements, depth + 1, written)?;
+                    Self::write_symbol('}', depth + 1, written)?;
+                }
+                XmlWriter::write_close("ifStatement", depth, written)
+            }
+            Statement::While(statement) => {
+                XmlWriter::write_open("whileStatement", depth, written)?;
+                Self::write_keyword("while", depth + 1, written)?;
+                Self::write_symbol('(', depth + 1, written)?;
+                Self::write_expression(&statement.condition, depth + 1, written)?;
+                Self::write_symbol(')', depth + 1, written)?;
+                Self::write_symbol('{', depth + 1, written)?;
+                Self::write_statements(&statement.statements, depth + 1, written)?;
+                Self::write_symbol('}', depth + 1, written)?;
+                XmlWriter::write_close("whileStatement", depth, written)
+            }
+            Statement::Do(statement) => {
+                XmlWriter::write_open("doStatement", depth, written)?;
+                Self::write_keyword("do", depth + 1, written)?;
+                Self::write_subroutine_call(&statement.subroutine_call, depth + 1, written)?;
+                Self::write_symbol(';', depth + 1, written)?;
+                XmlWriter::write_close("doStatement", depth, written)
+            }
+            Statement::Return(statement) => {
+                XmlWriter::write_open("returnStatement", depth, written)?;
+                Self::write_keyword("return", depth + 1, written)?;
+                if let Some(value) = &statement.value {
+                    Self::write_expression(value, depth + 1, written)?;
+                }
+                Self::write_symbol(';', depth + 1, written)?;
+                XmlWriter::write_close("returnStatement", depth, written)
+            }
+        }
+    }
+
+    fn write_expression(
+        expression: &Expression,
+        depth: usize,
+        written: &mut impl Write,
+    ) -> Result<()> {
+        XmlWriter::write_open("expression", depth, written)?;
+        Self::write_operands(expression, depth + 1, written)?;
+        XmlWriter::write_close("expression", depth, written)
+    }
+
+    /// Writes `term (op term)*` flat, as the grammar has no element for a binary operation.
+    fn write_operands(
+        expression: &Expression,
+        depth: usize,
+        written: &mut impl Write,
+    ) -> Result<()> {
+        match expression {
+            Expression::Term(term) => Self::write_term(term, depth, written),
+            Expression::Binary(binary) => {
+                Self::write_operands(&binary.lhs, depth, written)?;
+                XmlWriter::write_element("symbol", binary.op.symbol(), depth, written)?;
+                Self::write_operands(&binary.rhs, depth, written)
+            }
+        }
+    }
+
+    fn write_term(term: &Term, depth: usize, written: &mut impl Write) -> Result<()> {
+        XmlWriter::write_open("term", depth, written)?;
+        match term {
+            Term::IntegerConstant(value, _) | Term::BitPattern(value, _) => {
+                XmlWriter::write_element("integerConstant", &value.to_string(), depth + 1, written)?
+            }
+            Term::CharConstant(value, _) => {
+                XmlWriter::write_element("charConstant", &value.to_string(), depth + 1, written)?
+            }
+            Term::StringConstant(value, _) => {
+                XmlWriter::write_element("stringConstant", value, depth + 1, written)?
+            }
+            Term::KeywordConstant(keyword_constant, _) => {
+                let keyword = match keyword_constant {
+                    KeywordConstant::True => "true",
+                    KeywordConstant::False => "false",
+                    KeywordConstant::Null => "null",
+                    KeywordConstant::This => "this",
+                };
+                Self::write_keyword(keyword, depth + 1, written)?
+            }
+            Term::VarName(var_name) => Self::write_identifier(var_name, depth + 1, written)?,
+            Term::ArrayElement(var_name, index, _) => {
+                Self::write_identifier(var_name, depth + 1, written)?;
+                Self::write_symbol('[', depth + 1, written)?;
+                Self::write_expression(index, depth + 1, written)?;
+                Self::write_symbol(']', depth + 1, written)?;
+            }
+            Term::SubroutineCall(subroutine_call) => {
+                Self::write_subroutine_call(subroutine_call, depth + 1, written)?
+            }
+            Term::Parenthesized(expression, _) => {
+                Self::write_symbol('(', depth + 1, written)?;
+                Self::write_expression(expression, depth + 1, written)?;
+                Self::write_symbol(')', depth + 1, written)?;
+            }
+            Term::Unary(op, term, _) => {
+                Self::write_symbol(op.symbol(), depth + 1, written)?;
+                Self::write_term(term, depth + 1, written)?;
+            }
+        }
+        XmlWriter::write_close("term", depth, written)
+    }
+
+    /// subroutineCall has no element of its own, its tokens belong to the enclosing one.
+    fn write_subroutine_call(
+        subroutine_call: &SubroutineCall,
+        depth: usize,
+        written: &mut impl Write,
+    ) -> Result<()> {
+        if let Some(receiver) = &subroutine_call.receiver {
+            Self::write_identifier(receiver, depth, written)?;
+            Self::write_symbol('.', depth, written)?;
+        }
+        Self::write_identifier(&subroutine_call.subroutine_name, depth, written)?;
+        Self::write_symbol('(', depth, written)?;
+        XmlWriter::write_open("expressionList", depth, written)?;
+        for (i, argument) in subroutine_call.arguments.iter().enumerate() {
+            if i > 0 {
+                Self::write_symbol(',', depth + 1, written)?;
+            }
+            Self::write_expression(argument, depth + 1, written)?;
+        }
+        XmlWriter::write_close("expressionList", depth, written)?;
+        Self::write_symbol(')', depth, written)
+    }
+
+    fn write_var_names(
+        var_names: &[Identifier],
+        depth: usize,
+        written: &mut impl Write,
+    ) -> Result<()> {
+        for (i, var_name) in var_names.iter().enumerate() {
+            if i > 0 {
+                Self::write_symbol(',', depth, written)?;
+            }
+            Self::write_identifier(var_name, depth, written)?;
+        }
+        Ok(())
+    }
+
+    fn write_type(type_name: &TypeName, depth: usize, written: &mut impl Write) -> Result<()> {
+        match type_name {
+            TypeName::Class(class_name) => {
+                XmlWriter::write_element("identifier", class_name, depth, written)
+            }
+            _ => Self::write_keyword(&type_name.to_string(), depth, written),
+        }
+    }
+
+    fn write_keyword(keyword: &str, depth: usize, written: &mut impl Write) -> Result<()> {
+        XmlWriter::write_element("keyword", keyword, depth, written)
+    }
+
+    fn write_symbol(symbol: char, depth: usize, written: &mut impl Write) -> Result<()> {
+        XmlWriter::write_element("symbol", &symbol.to_string(), depth, written)
+    }
+
+    fn write_identifier(
+        identifier: &Identifier,
+        depth: usize,
+        written: &mut impl Write,
+    ) -> Result<()> {
+        XmlWriter::write_element("identifier", &identifier.name, depth, written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::parsing::class_parser::ClassParser;
+    use crate::parsing::precedence::Precedence;
+    use crate::tokenizer::dialect::Dialect;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+    use crate::writer::parse_tree_writer::ParseTreeWriter;
+
+    #[test]
+    fn can_write_class() {
+        let expected = "\
+<class>
+  <keyword> class </keyword>
+  <identifier> Main </identifier>
+  <symbol> { </symbol>
+  <classVarDec>
+    <keyword> static </keyword>
+    <keyword> int </keyword>
+    <identifier> x </identifier>
+    <symbol> , </symbol>
+    <identifier> y </identifier>
+    <symbol> ; </symbol>
+  </classVarDec>
+  <subroutineDec>
+    <keyword> function </keyword>
+    <keyword> void </keyword>
+    <identifier> main </identifier>
+    <symbol> ( </symbol>
+    <parameterList>
+    </parameterList>
+    <symbol> ) </symbol>
+    <subroutineBody>
+      <symbol> { </symbol>
+      <statements>
+        <letStatement>
+          <keyword> let </keyword>
+          <identifier> x </identifier>
+          <symbol> = </symbol>
+          <expression>
+            <term>
+              <symbol> - </symbol>
+              <term>
+                <identifier> y </identifier>
+              </term>
+            </term>
+            <symbol> &lt; </symbol>
+            <term>
+              <identifier> Math </identifier>
+              <symbol> . </symbol>
+              <identifier> max </identifier>
+              <symbol> ( </symbol>
+              <expressionList>
+                <expression>
+                  <term>
+                    <integerConstant> 1 </integerConstant>
+                  </term>
+                </expression>
+                <symbol> , </symbol>
+                <expression>
+                  <term>
+                    <keyword> true </keyword>
+                  </term>
+                </expression>
+              </expressionList>
+              <symbol> ) </symbol>
+            </term>
+          </expression>
+          <symbol> ; </symbol>
+        </letStatement>
+        <returnStatement>
+          <keyword> return </keyword>
+          <symbol> ; </symbol>
+        </returnStatement>
+      </statements>
+      <symbol> } </symbol>
+    </subroutineBody>
+  </subroutineDec>
+  <symbol> } </symbol>
+</class>
+";
+
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Main {{").unwrap();
+        writeln!(src_file, "    static int x, y;").unwrap();
+        writeln!(src_file, "    function void main() {{").unwrap();
+        writeln!(src_file, "        let x = -y < Math.max(1, true);").unwrap();
+        writeln!(src_file, "        return;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+        let mut output = Vec::<u8>::new();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+        let class = ClassParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+
+        let result = ParseTreeWriter::write_class(&class, &mut output);
+        let actual = String::from_utf8(output).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_write_bit_patterns_with_their_value() {
+        let expected = "\
+          <expression>
+            <term>
+              <integerConstant> 65535 </integerConstant>
+            </term>
+          </expression>
+";
+
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "class Main {{").unwrap();
+        writeln!(src_file, "    function void main() {{").unwrap();
+        writeln!(src_file, "        let x = 0xFFFF;").unwrap();
+        writeln!(src_file, "        return;").unwrap();
+        writeln!(src_file, "    }}").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+        let mut output = Vec::<u8>::new();
+
+        let mut tokenizer = JackTokenizer::with_dialect(path, Dialect::Extended).unwrap();
+        let class = ClassParser::parse(&mut tokenizer, Precedence::Jack).unwrap();
+
+        let result = ParseTreeWriter::write_class(&class, &mut output);
+        let actual = String::from_utf8(output).unwrap();
+
+        assert!(result.is_ok());
+        assert!(actual.contains(expected));
+    }
+}
@@ -3,7 +3,7 @@ use std::fmt::{Display, Formatter};
 use crate::symbol_table::kind::Kind;
 
 #[allow(dead_code)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Segment {
     Constant,
     Argument,
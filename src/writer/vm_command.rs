@@ -0,0 +1,36 @@
+use std::fmt::{Display, Formatter};
+
+use crate::writer::command::Command;
+use crate::writer::segment::Segment;
+
+/// One instruction of the VM language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VmCommand {
+    Push(Segment, usize),
+    Pop(Segment, usize),
+    Arithmetic(Command),
+    Label(String),
+    Goto(String),
+    IfGoto(String),
+    /// A function name and its number of local variables
+    Function(String, usize),
+    /// A function name and its number of arguments
+    Call(String, usize),
+    Return,
+}
+
+impl Display for VmCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VmCommand::Push(segment, index) => write!(f, "push {segment} {index}"),
+            VmCommand::Pop(segment, index) => write!(f, "pop {segment} {index}"),
+            VmCommand::Arithmetic(command) => write!(f, "{command}"),
+            VmCommand::Label(label) => write!(f, "label {label}"),
+            VmCommand::Goto(label) => write!(f, "goto {label}"),
+            VmCommand::IfGoto(label) => write!(f, "if-goto {label}"),
+            VmCommand::Function(name, n_locals) => write!(f, "function {name} {n_locals}"),
+            VmCommand::Call(name, n_args) => write!(f, "call {name} {n_args}"),
+            VmCommand::Return => write!(f, "return"),
+        }
+    }
+}
@@ -2,54 +2,16 @@ use std::io::Write;
 
 use anyhow::Result;
 
-use crate::writer::command::Command;
-use crate::writer::segment::Segment;
+use crate::writer::vm_command::VmCommand;
 
+/// Serializes VM commands to the text of a `.vm` file, one command per line.
 pub struct VmWriter {}
 
 impl VmWriter {
-    pub fn write_push(segment: &Segment, index: usize, written: &mut impl Write) -> Result<()> {
-        writeln!(written, "push {segment} {index}")?;
-        Ok(())
-    }
-
-    pub fn write_pop(segment: &Segment, index: usize, written: &mut impl Write) -> Result<()> {
-        writeln!(written, "pop {segment} {index}")?;
-        Ok(())
-    }
-
-    pub fn write_arithmetic(command: &Command, written: &mut impl Write) -> Result<()> {
-        writeln!(written, "{command}")?;
-        Ok(())
-    }
-
-    pub fn write_label(label: &str, written: &mut impl Write) -> Result<()> {
-        writeln!(written, "label {label}")?;
-        Ok(())
-    }
-
-    pub fn write_goto(label: &str, written: &mut impl Write) -> Result<()> {
-        writeln!(written, "goto {label}")?;
-        Ok(())
-    }
-
-    pub fn write_if(label: &str, written: &mut impl Write) -> Result<()> {
-        writeln!(written, "if-goto {label}")?;
-        Ok(())
-    }
-
-    pub fn write_call(name: &str, n_args: usize, written: &mut impl Write) -> Result<()> {
-        writeln!(written, "call {name} {n_args}")?;
-        Ok(())
-    }
-
-    pub fn write_function(name: &str, n_locals: usize, written: &mut impl Write) -> Result<()> {
-        writeln!(written, "function {name} {n_locals}")?;
-        Ok(())
-    }
-
-    pub fn write_return(written: &mut impl Write) -> Result<()> {
-        writeln!(written, "return")?;
+    pub fn write(commands: &[VmCommand], written: &mut impl Write) -> Result<()> {
+        for command in commands {
+            writeln!(written, "{command}")?;
+        }
         Ok(())
     }
 }
@@ -58,12 +20,13 @@ impl VmWriter {
 mod tests {
     use crate::writer::command::Command;
     use crate::writer::segment::Segment;
+    use crate::writer::vm_command::VmCommand;
     use crate::writer::vm_writer::VmWriter;
 
     #[test]
     fn can_write_push() {
         let mut output = Vec::<u8>::new();
-        VmWriter::write_push(&Segment::Argument, 0, &mut output).unwrap();
+        VmWriter::write(&[VmCommand::Push(Segment::Argument, 0)], &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
         assert_eq!("push argument 0\n", actual)
     }
@@ -71,7 +34,7 @@ mod tests {
     #[test]
     fn can_write_pop() {
         let mut output = Vec::<u8>::new();
-        VmWriter::write_pop(&Segment::This, 1, &mut output).unwrap();
+        VmWriter::write(&[VmCommand::Pop(Segment::This, 1)], &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
         assert_eq!("pop this 1\n", actual)
     }
@@ -79,15 +42,35 @@ mod tests {
     #[test]
     fn can_write_arithmetic() {
         let mut output = Vec::<u8>::new();
-        VmWriter::write_arithmetic(&Command::Add, &mut output).unwrap();
+        VmWriter::write(&[VmCommand::Arithmetic(Command::Add)], &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
         assert_eq!("add\n", actual)
     }
 
+    #[test]
+    fn can_write_flow() {
+        let commands = [
+            VmCommand::Label(String::from("WHILE_EXP0")),
+            VmCommand::IfGoto(String::from("WHILE_END0")),
+            VmCommand::Goto(String::from("WHILE_EXP0")),
+        ];
+        let mut output = Vec::<u8>::new();
+        VmWriter::write(&commands, &mut output).unwrap();
+        let actual = String::from_utf8(output).unwrap();
+        assert_eq!(
+            "label WHILE_EXP0\nif-goto WHILE_END0\ngoto WHILE_EXP0\n",
+            actual
+        )
+    }
+
     #[test]
     fn can_write_call() {
         let mut output = Vec::<u8>::new();
-        VmWriter::write_call("Math.multiply", 2, &mut output).unwrap();
+        VmWriter::write(
+            &[VmCommand::Call(String::from("Math.multiply"), 2)],
+            &mut output,
+        )
+        .unwrap();
         let actual = String::from_utf8(output).unwrap();
         assert_eq!("call Math.multiply 2\n", actual)
     }
@@ -95,7 +78,11 @@ mod tests {
     #[test]
     fn can_write_function() {
         let mut output = Vec::<u8>::new();
-        VmWriter::write_function("Main.main", 2, &mut output).unwrap();
+        VmWriter::write(
+            &[VmCommand::Function(String::from("Main.main"), 2)],
+            &mut output,
+        )
+        .unwrap();
         let actual = String::from_utf8(output).unwrap();
         assert_eq!("function Main.main 2\n", actual)
     }
@@ -103,7 +90,7 @@ mod tests {
     #[test]
     fn can_write_return() {
         let mut output = Vec::<u8>::new();
-        VmWriter::write_return(&mut output).unwrap();
+        VmWriter::write(&[VmCommand::Return], &mut output).unwrap();
         let actual = String::from_utf8(output).unwrap();
         assert_eq!("return\n", actual)
     }
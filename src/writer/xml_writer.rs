@@ -0,0 +1,150 @@
+use std::io::Write;
+
+use anyhow::{bail, Result};
+
+use crate::tokenizer::jack_tokenizer::JackTokenizer;
+use crate::tokenizer::token::Token;
+use crate::tokenizer::token_type::TokenType;
+
+/// Writes the XML files compared by the nand2tetris project 10 tools.
+pub struct XmlWriter {}
+
+impl XmlWriter {
+    /// Writes every remaining token of the tokenizer inside `<tokens>`, unless the source has lexical errors.
+    pub fn write_tokens(tokenizer: &mut JackTokenizer, written: &mut impl Write) -> Result<()> {
+        let diagnostics = tokenizer.take_diagnostics();
+        if !diagnostics.is_empty() {
+            bail!(diagnostics)
+        }
+
+        writeln!(written, "<tokens>")?;
+        while tokenizer.has_more_tokens()? {
+            Self::write_token(tokenizer.peek()?, written)?;
+            tokenizer.advance()?;
+        }
+        writeln!(written, "</tokens>")?;
+        Ok(())
+    }
+
+    fn write_token(token: &Token, written: &mut impl Write) -> Result<()> {
+        Self::write_element(Self::tag_of(token.token_type()), token.value(), 0, written)
+    }
+
+    /// Writes a terminal element indented by `depth` levels.
+    pub fn write_element(
+        tag: &str,
+        value: &str,
+        depth: usize,
+        written: &mut impl Write,
+    ) -> Result<()> {
+        let indent = Self::indent(depth);
+        writeln!(written, "{indent}<{tag}> {} </{tag}>", Self::escape(value))?;
+        Ok(())
+    }
+
+    pub fn write_open(tag: &str, depth: usize, written: &mut impl Write) -> Result<()> {
+        writeln!(written, "{}<{tag}>", Self::indent(depth))?;
+        Ok(())
+    }
+
+    pub fn write_close(tag: &str, depth: usize, written: &mut impl Write) -> Result<()> {
+        writeln!(written, "{}</{tag}>", Self::indent(depth))?;
+        Ok(())
+    }
+
+    fn indent(depth: usize) -> String {
+        "  ".repeat(depth)
+    }
+
+    fn tag_of(token_type: &TokenType) -> &'static str {
+        match token_type {
+            TokenType::Keyword => "keyword",
+            TokenType::Symbol => "symbol",
+            TokenType::Identifier => "identifier",
+            TokenType::IntConst => "integerConstant",
+            TokenType::StringConst => "stringConstant",
+            TokenType::CharConst => "charConstant",
+        }
+    }
+
+    fn escape(value: &str) -> String {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use crate::tokenizer::dialect::Dialect;
+    use crate::tokenizer::jack_tokenizer::JackTokenizer;
+    use crate::writer::xml_writer::XmlWriter;
+
+    #[test]
+    fn can_write_tokens() {
+        let expected = "\
+<tokens>
+<keyword> if </keyword>
+<symbol> ( </symbol>
+<identifier> x </identifier>
+<symbol> &lt; </symbol>
+<integerConstant> 0 </integerConstant>
+<symbol> &amp; </symbol>
+<identifier> y </identifier>
+<symbol> &gt; </symbol>
+<integerConstant> 1 </integerConstant>
+<symbol> ) </symbol>
+<symbol> { </symbol>
+<keyword> let </keyword>
+<identifier> s </identifier>
+<symbol> = </symbol>
+<stringConstant> string constant </stringConstant>
+<symbol> ; </symbol>
+<symbol> } </symbol>
+</tokens>
+";
+
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, "if (x < 0 & y > 1) {{").unwrap();
+        writeln!(src_file, "    let s = \"string constant\"; // comment").unwrap();
+        writeln!(src_file, "}}").unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+        let mut output = Vec::<u8>::new();
+
+        let mut tokenizer = JackTokenizer::new(path).unwrap();
+
+        let result = XmlWriter::write_tokens(&mut tokenizer, &mut output);
+        let actual = String::from_utf8(output).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_escape_quotes() {
+        let expected = "\
+<tokens>
+<stringConstant> say &quot;hi&quot; &amp; go </stringConstant>
+</tokens>
+";
+
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(src_file, r#""say \"hi\" & go""#).unwrap();
+        src_file.rewind().unwrap();
+        let path = src_file.path();
+        let mut output = Vec::<u8>::new();
+
+        let mut tokenizer = JackTokenizer::with_dialect(path, Dialect::Extended).unwrap();
+
+        let result = XmlWriter::write_tokens(&mut tokenizer, &mut output);
+        let actual = String::from_utf8(output).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(expected, actual);
+    }
+}